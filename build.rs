@@ -0,0 +1,79 @@
+use std::{env, fs, io, path::Path};
+
+// Discovers src/yYYYY/dayNN.rs and generates the `years` module body:
+// one module per year with a `mod` declaration per day, plus the REGISTRY
+// dispatch table. Each day module is expected to export a unit struct
+// `DayNN` implementing `Solver`.
+fn main() -> io::Result<()> {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src_dir = Path::new(&manifest_dir).join("src");
+    // Also catches new year directories
+    println!("cargo::rerun-if-changed={}", src_dir.display());
+
+    let mut days = Vec::new();
+    for entry in fs::read_dir(&src_dir)? {
+        let year_dir = entry?.path();
+        let Some(year) = parse_year(&year_dir) else {
+            continue;
+        };
+        for entry in fs::read_dir(&year_dir)? {
+            let path = entry?.path();
+            if let Some(day) = parse_day(&path) {
+                days.push((year, day, path));
+            }
+        }
+    }
+    days.sort();
+
+    let mut registry = String::new();
+    let mut years: Vec<usize> = days.iter().map(|(year, _, _)| *year).collect();
+    years.dedup();
+    for year in years {
+        registry.push_str(&format!("pub mod y{year} {{\n"));
+        for (_, day, path) in days.iter().filter(|(y, _, _)| *y == year) {
+            registry.push_str(&format!(
+                "    #[path = {:?}]\n    pub mod day{day:02};\n",
+                path.display().to_string()
+            ));
+        }
+        registry.push_str("}\n");
+    }
+    registry.push_str(&format!(
+        "\npub(crate) static REGISTRY: [(usize, usize, &dyn crate::Run); {}] = [\n",
+        days.len()
+    ));
+    for (year, day, _) in &days {
+        registry.push_str(&format!(
+            "    ({year}, {day}, &y{year}::day{day:02}::Day{day:02}),\n"
+        ));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("years.rs"), registry)
+}
+
+fn parse_year(path: &Path) -> Option<usize> {
+    if !path.is_dir() {
+        return None;
+    }
+    let digits = path.file_name()?.to_str()?.strip_prefix('y')?;
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn parse_day(path: &Path) -> Option<usize> {
+    if path.extension()? != "rs" {
+        return None;
+    }
+    let digits = path.file_stem()?.to_str()?.strip_prefix("day")?;
+    if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    match digits.parse() {
+        Ok(0) | Err(_) => None,
+        Ok(day) => Some(day),
+    }
+}
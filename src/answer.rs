@@ -0,0 +1,134 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    Coordinate {
+        x: usize,
+        y: usize,
+    },
+    /// The puzzle has no answer for this input, e.g. no path exists.
+    Absent(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(x) => write!(f, "{x}"),
+            Self::Signed(x) => write!(f, "{x}"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Coordinate { x, y } => write!(f, "{x},{y}"),
+            Self::Absent(reason) => write!(f, "none ({reason})"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Unsigned(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Self::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+// Compare numerically regardless of signedness, e.g. `assert_eq!(answer, 480)`
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        match *self {
+            Self::Unsigned(x) => x == *other,
+            Self::Signed(x) => u64::try_from(x).is_ok_and(|x| x == *other),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        match *self {
+            Self::Unsigned(x) => i64::try_from(x).is_ok_and(|x| x == *other),
+            Self::Signed(x) => x == *other,
+            _ => false,
+        }
+    }
+}
+
+// Unsuffixed literals are i32
+impl PartialEq<i32> for Answer {
+    fn eq(&self, other: &i32) -> bool {
+        *self == i64::from(*other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Self::Text(text) if text == other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_with_literals() {
+        assert_eq!(Answer::from(42u64), 42);
+        assert_eq!(Answer::from(42i64), 42);
+        assert_ne!(Answer::from(42u64), 43);
+        assert_eq!(Answer::from(-7i64), -7);
+        assert_ne!(Answer::from(u64::MAX), -1);
+        assert_ne!(Answer::Text("42".into()), 42);
+    }
+
+    #[test]
+    fn compares_across_signedness() {
+        assert_eq!(Answer::Unsigned(480), 480i64);
+        assert_eq!(Answer::Signed(480), 480u64);
+        assert_ne!(Answer::Signed(-1), u64::MAX);
+        assert_ne!(Answer::Unsigned(u64::MAX), -1i64);
+        assert_ne!(Answer::Absent("no path".into()), 0u64);
+    }
+
+    #[test]
+    fn compares_text() {
+        assert_eq!(Answer::from("4,6,3".to_string()), "4,6,3");
+        assert_ne!(Answer::Text("4,6,3".into()), "4,6");
+        assert_ne!(Answer::Coordinate { x: 6, y: 1 }, "6,1");
+    }
+
+    #[test]
+    fn displays_as_submitted() {
+        assert_eq!(Answer::Unsigned(11).to_string(), "11");
+        assert_eq!(Answer::Signed(-3).to_string(), "-3");
+        assert_eq!(Answer::Text("4,6,3".into()).to_string(), "4,6,3");
+        assert_eq!(Answer::Coordinate { x: 6, y: 1 }.to_string(), "6,1");
+        let absent = Answer::Absent("no path to the exit".into());
+        assert_eq!(absent.to_string(), "none (no path to the exit)");
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{solve_with, Context, Part, SolveError, FIRST_YEAR};
+
+/// Timing statistics over repeated runs of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+}
+
+/// Saved statistics to compare later runs against, keyed by year and day,
+/// e.g. `[2024.13]`. A day without a year, e.g. `[13]`, is from `FIRST_YEAR`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    years: BTreeMap<String, BTreeMap<String, Stats>>,
+}
+
+#[derive(Debug)]
+pub enum BaselineError {
+    Parse(toml::de::Error),
+    Write(toml::ser::Error),
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let runs = samples.len();
+        let total: Duration = samples.iter().sum();
+        Some(Stats {
+            runs,
+            min: *samples.first()?,
+            median: samples[runs / 2],
+            mean: total / runs as u32,
+            // Nearest rank
+            p95: samples[(runs * 95).div_ceil(100) - 1],
+        })
+    }
+
+    /// How much slower (positive) or faster (negative) this median is
+    /// than `baseline`'s, in percent, or `None` if the baseline's is zero.
+    pub fn change(&self, baseline: &Stats) -> Option<f64> {
+        let before = baseline.median.as_secs_f64();
+        (before > 0.0).then(|| (self.median.as_secs_f64() - before) / before * 100.0)
+    }
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Self, BaselineError> {
+        let raw: toml::Table = toml::from_str(text)?;
+        let mut baseline = Baseline::default();
+        for (key, value) in raw {
+            match key.parse::<usize>() {
+                // Saved before there were years
+                Ok(day @ 1..=25) => {
+                    let days = baseline.years.entry(FIRST_YEAR.to_string()).or_default();
+                    days.insert(day.to_string(), value.try_into()?);
+                }
+                _ => {
+                    let days = baseline.years.entry(key).or_default();
+                    days.extend(value.try_into::<BTreeMap<_, _>>()?);
+                }
+            }
+        }
+        Ok(baseline)
+    }
+
+    pub fn to_toml(&self) -> Result<String, BaselineError> {
+        Ok(toml::to_string(&self.years)?)
+    }
+
+    pub fn get(&self, year: usize, day: usize) -> Option<&Stats> {
+        self.years.get(&year.to_string())?.get(&day.to_string())
+    }
+
+    pub fn insert(&mut self, year: usize, day: usize, stats: Stats) {
+        let days = self.years.entry(year.to_string()).or_default();
+        days.insert(day.to_string(), stats);
+    }
+}
+
+/// Solves `input` `warmup` times untimed, then `runs` times timed.
+/// Stops at the first error, since a failing day has nothing to measure.
+pub fn bench(
+    year: usize,
+    day: usize,
+    input: &str,
+    parts: &[Part],
+    cx: &Context,
+    warmup: usize,
+    runs: usize,
+) -> Result<Stats, SolveError> {
+    let solve = || -> Result<Duration, SolveError> {
+        let start = Instant::now();
+        let solution = solve_with(year, day, input, parts, cx)?;
+        let elapsed = start.elapsed();
+        for (_, result) in solution.parts() {
+            result?;
+        }
+        Ok(elapsed)
+    };
+    for _ in 0..warmup {
+        solve()?;
+    }
+    let samples = (0..runs).map(|_| solve()).collect::<Result<_, _>>()?;
+    Ok(Stats::from_samples(samples).expect("at least one run"))
+}
+
+impl fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::Write(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {}
+
+impl From<toml::de::Error> for BaselineError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Parse(value)
+    }
+}
+
+impl From<toml::ser::Error> for BaselineError {
+    fn from(value: toml::ser::Error) -> Self {
+        Self::Write(value)
+    }
+}
+
+// Durations are stored as whole nanoseconds, e.g. `median = 1520300`
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos().try_into().unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
@@ -0,0 +1,272 @@
+use std::{fmt, time::Duration};
+
+use advent_of_code_2024::Part;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Where puzzles come from when the base URL isn't overridden.
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// The site asks automated tools to say what they are and where to find out more
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    "; downloads each input once and checks answers against past attempts before submitting)"
+);
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Talks to the puzzle site as the user the session token belongs to.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+pub enum ClientError {
+    Request { url: String, error: ureq::Error },
+    SessionRejected(String),
+    NotFound(String),
+    Status { url: String, status: u16 },
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too soon after the last attempt
+    RateLimited,
+    /// The part was solved before, so the answer wasn't checked
+    AlreadySolved,
+    /// A response none of the above match
+    Unrecognised,
+}
+
+pub struct Verdict {
+    pub outcome: Outcome,
+    /// The site's response, as text
+    pub message: String,
+    /// How long until the next answer will be looked at, if the site says
+    pub wait: Option<Duration>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(TIMEOUT))
+            // Statuses are errors with their own meaning here
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// The day's puzzle input, as given to this user.
+    pub fn input(&self, year: usize, day: usize) -> Result<String, ClientError> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let request = |error| ClientError::Request {
+            url: url.clone(),
+            error,
+        };
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(request)?;
+        match response.status().as_u16() {
+            200 => response.body_mut().read_to_string().map_err(request),
+            // The site answers a missing or expired session with 400
+            400 | 401 | 403 => Err(ClientError::SessionRejected(url)),
+            404 => Err(ClientError::NotFound(url)),
+            status => Err(ClientError::Status { url, status }),
+        }
+    }
+
+    /// Submits an answer to one part of the day's puzzle.
+    pub fn submit(
+        &self,
+        year: usize,
+        day: usize,
+        part: Part,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let request = |error| ClientError::Request {
+            url: url.clone(),
+            error,
+        };
+        let level = part.number().to_string();
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .map_err(request)?;
+        match response.status().as_u16() {
+            200 => Ok(Verdict::parse(
+                &response.body_mut().read_to_string().map_err(request)?,
+            )),
+            400 | 401 | 403 => Err(ClientError::SessionRejected(url)),
+            404 => Err(ClientError::NotFound(url)),
+            status => Err(ClientError::Status { url, status }),
+        }
+    }
+}
+
+impl Verdict {
+    // The response is a page with the verdict in its <article>
+    fn parse(html: &str) -> Self {
+        let article_re = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+        let tag_re = Regex::new(r"<[^>]*>").unwrap();
+        // "You have 4m 32s left to wait" after answering too soon
+        let left_re = Regex::new(r"(?i)you have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        // "Please wait one minute" or "please wait 5 minutes" after a wrong answer
+        let wait_re = Regex::new(r"(?i)please wait (one|\d+) minutes?").unwrap();
+
+        let article = article_re
+            .captures(html)
+            .map_or(html, |captures| captures.get(1).unwrap().as_str());
+        let text = tag_re.replace_all(article, "");
+        let message = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let lower = message.to_lowercase();
+        let outcome = if lower.contains("that's the right answer") {
+            Outcome::Right
+        } else if lower.contains("answer too recently") {
+            Outcome::RateLimited
+        } else if lower.contains("solving the right level") {
+            Outcome::AlreadySolved
+        } else if lower.contains("not the right answer") {
+            match () {
+                _ if lower.contains("too high") => Outcome::TooHigh,
+                _ if lower.contains("too low") => Outcome::TooLow,
+                _ => Outcome::Wrong,
+            }
+        } else {
+            Outcome::Unrecognised
+        };
+        let wait = match left_re.captures(&message) {
+            Some(captures) => {
+                let number = |i| {
+                    captures
+                        .get(i)
+                        .map_or(0, |m: regex::Match| m.as_str().parse().unwrap_or(0))
+                };
+                Some(Duration::from_secs(number(1) * 60 + number(2)))
+            }
+            None => wait_re.captures(&message).map(|captures| {
+                let minutes = captures[1].parse().unwrap_or(1);
+                Duration::from_secs(minutes * 60)
+            }),
+        };
+        Verdict {
+            outcome,
+            message,
+            wait,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = match self {
+            Outcome::Right => "right",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::RateLimited => "rate limited",
+            Outcome::AlreadySolved => "already solved",
+            Outcome::Unrecognised => "unrecognised",
+        };
+        f.write_str(outcome)
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Request { url, error } => write!(f, "Error requesting {url}: {error}"),
+            ClientError::SessionRejected(url) => {
+                write!(
+                    f,
+                    "{url} rejected the session token. Log in again and copy a new one."
+                )
+            }
+            ClientError::NotFound(url) => {
+                write!(
+                    f,
+                    "{url} was not found. The puzzle may not be unlocked yet."
+                )
+            }
+            ClientError::Status { url, status } => {
+                write!(f, "{url} answered with status {status}.")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // As the site sends it, cut down to the <article>
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn parses_right_answers() {
+        let verdict = Verdict::parse(&page(
+            "That's the right answer! You are <span class=\"day-success\">one gold star</span> \
+             closer to finding the Chief Historian. <a href=\"/2024/day/7#part2\">[Continue to Part Two]</a>",
+        ));
+        assert_eq!(verdict.outcome, Outcome::Right);
+        assert_eq!(verdict.wait, None);
+        assert!(verdict
+            .message
+            .starts_with("That's the right answer! You are one gold star"));
+    }
+
+    #[test]
+    fn parses_too_high_answers() {
+        let verdict = Verdict::parse(&page(
+            "That's not the right answer; your answer is too high. If you're stuck, make sure \
+             you're using the full input data. Please wait one minute before trying again. \
+             <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(verdict.outcome, Outcome::TooHigh);
+        assert_eq!(verdict.wait, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let verdict = Verdict::parse(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again. You have 1m 5s left to wait. \
+             <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(verdict.outcome, Outcome::RateLimited);
+        assert_eq!(verdict.wait, Some(Duration::from_secs(65)));
+    }
+
+    #[test]
+    fn parses_already_solved_parts() {
+        let verdict = Verdict::parse(&page(
+            "You don't seem to be solving the right level. Did you already complete it? \
+             <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(verdict.outcome, Outcome::AlreadySolved);
+        assert_eq!(verdict.wait, None);
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use advent_of_code_2024::*;
+use serde::Serialize;
+
+use super::{
+    batch_files, input_path, piped_input, pool, read_input, solve_input, Config, RunError, STDIN,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One day's run. Failures are reported here rather than aborting,
+/// so every requested day gets a record.
+#[derive(Serialize)]
+pub struct Record {
+    year: usize,
+    day: usize,
+    input: String,
+    parts: Vec<PartRecord>,
+    parse_ns: Option<u64>,
+    prepare_ns: Option<u64>,
+    total_ns: Option<u64>,
+    error: Option<ErrorRecord>,
+}
+
+#[derive(Serialize)]
+struct PartRecord {
+    part: usize,
+    answer: Option<String>,
+    time_ns: Option<u64>,
+    cached: bool,
+    error: Option<ErrorRecord>,
+}
+
+#[derive(Serialize)]
+struct ErrorRecord {
+    kind: &'static str,
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl Record {
+    pub fn new(year: usize, day: usize, input: &str, result: Result<Solution, RunError>) -> Self {
+        let mut record = Record {
+            year,
+            day,
+            input: input.to_string(),
+            parts: Vec::new(),
+            parse_ns: None,
+            prepare_ns: None,
+            total_ns: None,
+            error: None,
+        };
+        let solution = match result {
+            Ok(solution) => solution,
+            Err(err) => {
+                record.error = Some(ErrorRecord::from(&err));
+                return record;
+            }
+        };
+        let timings = solution.timings;
+        let cached = solution.cached.clone();
+        // Nothing was timed if every part came from the cache
+        let solved = [&solution.part1, &solution.part2]
+            .iter()
+            .filter(|result| result.is_some())
+            .count();
+        if solved > cached.len() {
+            record.parse_ns = Some(nanos(timings.parse));
+            record.prepare_ns = Some(nanos(timings.prepare));
+            record.total_ns = Some(nanos(timings.total()));
+        }
+        for (part, result) in solution.parts() {
+            let (answer, error) = match result {
+                Ok(answer) => (Some(answer.to_string()), None),
+                Err(err) => (None, Some(ErrorRecord::from(&err))),
+            };
+            record.parts.push(PartRecord {
+                part: part.number(),
+                answer,
+                time_ns: timings.part(part).map(nanos),
+                cached: cached.contains(&part),
+                error,
+            });
+        }
+        record
+    }
+
+    fn failed(&self) -> bool {
+        self.error.is_some() || self.parts.iter().any(|part| part.error.is_some())
+    }
+
+    /// The kind of each error in the record, the day's first.
+    pub fn error_kinds(&self) -> Vec<&'static str> {
+        let parts = self.parts.iter().map(|part| &part.error);
+        [&self.error]
+            .into_iter()
+            .chain(parts)
+            .filter_map(|error| Some(error.as_ref()?.kind))
+            .collect()
+    }
+
+    // One row per part, or a single row without a part if the day failed outright
+    fn csv_rows(&self) -> Vec<String> {
+        let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let common = [
+            self.year.to_string(),
+            self.day.to_string(),
+            csv_field(&self.input),
+            optional(self.parse_ns),
+            optional(self.prepare_ns),
+        ]
+        .join(",");
+        let error_fields = |error: &Option<ErrorRecord>| match error {
+            Some(error) => [error.kind.to_string(), csv_field(&error.message)].join(","),
+            None => ",".to_string(),
+        };
+        if self.parts.is_empty() {
+            return vec![format!("{common},,,,,{}", error_fields(&self.error))];
+        }
+        self.parts
+            .iter()
+            .map(|part| {
+                format!(
+                    "{common},{},{},{},{},{}",
+                    part.part,
+                    csv_field(part.answer.as_deref().unwrap_or_default()),
+                    optional(part.time_ns),
+                    part.cached,
+                    error_fields(&part.error)
+                )
+            })
+            .collect()
+    }
+}
+
+const CSV_HEADER: &str =
+    "year,day,input,parse_ns,prepare_ns,part,answer,time_ns,cached,error_kind,error";
+
+impl From<&SolveError> for ErrorRecord {
+    fn from(error: &SolveError) -> Self {
+        let kind = match error.kind {
+            ErrorKind::UnknownDay => "unknown_day",
+            ErrorKind::Input { .. } => "input",
+            ErrorKind::Param { .. } => "param",
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::TimedOut(_) => "timeout",
+            ErrorKind::Other(_) => "other",
+        };
+        ErrorRecord {
+            kind,
+            message: error.kind.to_string(),
+            line: error.location().map(|location| location.line),
+            column: error.location().map(|location| location.column),
+        }
+    }
+}
+
+impl From<&RunError> for ErrorRecord {
+    fn from(error: &RunError) -> Self {
+        let kind = match error {
+            RunError::SolveError { error, .. } => return ErrorRecord::from(error.as_ref()),
+            RunError::FileError { .. } => "file",
+            RunError::StdinError(_) => "stdin",
+            RunError::Panicked { .. } => "panic",
+            _ => "other",
+        };
+        ErrorRecord {
+            kind,
+            message: error.to_string(),
+            line: None,
+            column: None,
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// Solves each day and prints a record for it in `format`,
+/// in order as soon as each day is done for NDJSON and CSV,
+/// or all at once for JSON.
+pub fn run(config: &Config<'_>, format: Format) -> Result<(), RunError> {
+    let piped = piped_input(config)?;
+    let mut records = Vec::new();
+    let mut failed = 0;
+    if format == Format::Csv {
+        println!("{CSV_HEADER}");
+    }
+    // Each day's input, or with --batch, the day with each input
+    let inputs: Vec<(usize, Option<String>)> = match config.batch {
+        Some(dir) => batch_files(dir)?
+            .into_iter()
+            .map(|filename| (config.days[0], Some(filename)))
+            .collect(),
+        None => config.days.iter().map(|&day| (day, None)).collect(),
+    };
+    let solve_day = |(day, batch_file): &(usize, Option<String>)| {
+        let day = *day;
+        let (filename, input) = match (batch_file, config.filename, &piped) {
+            (Some(filename), _, _) => (filename.clone(), read_input(filename)),
+            (None, Some(filename), _) => (filename.to_string(), read_input(filename)),
+            (None, None, Some(input)) => (STDIN.to_string(), Ok(input.clone())),
+            (None, None, None) => {
+                let filename = input_path(config.pattern(), config.year, day);
+                let input = read_input(&filename);
+                (filename, input)
+            }
+        };
+        let cache = config.cache.as_ref();
+        let result = input.and_then(|input| {
+            solve_input(
+                config.year,
+                day,
+                filename.clone(),
+                input,
+                config.parts,
+                &config.cx,
+                cache,
+            )
+        });
+        Record::new(config.year, day, &filename, result)
+    };
+    pool::for_each_ordered(&inputs, config.jobs, solve_day, |record| {
+        if record.failed() {
+            failed += 1;
+        }
+        match format {
+            Format::Json => records.push(record),
+            Format::Ndjson => println!("{}", serde_json::to_string(&record).unwrap()),
+            Format::Csv => record.csv_rows().iter().for_each(|row| println!("{row}")),
+            Format::Text => unreachable!("text output doesn't use records"),
+        }
+    });
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&records).unwrap());
+    }
+    match (failed, config.batch) {
+        (0, _) => Ok(()),
+        (failed, Some(_)) => Err(RunError::InputsFailed {
+            failed,
+            total: inputs.len(),
+        }),
+        (failed, None) => Err(RunError::DaysFailed {
+            failed,
+            total: inputs.len(),
+        }),
+    }
+}
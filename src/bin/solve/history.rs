@@ -0,0 +1,249 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    time::Duration,
+};
+
+use advent_of_code_2024::Part;
+use serde::{Deserialize, Serialize};
+
+use super::client::Outcome;
+
+/// Every answer submitted, kept as a JSON object per line, oldest first,
+/// so the same mistake isn't sent to the site twice.
+pub struct History {
+    filename: String,
+    attempts: Vec<Attempt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    /// When the site will look at another answer for the day, if it said
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+}
+
+/// Why an answer wasn't submitted.
+pub enum Refusal {
+    AlreadyRight(String),
+    AlreadySolved,
+    KnownWrong(Outcome),
+    AtLeast(i128),
+    AtMost(i128),
+    Wait(Duration),
+}
+
+impl History {
+    /// Reads the history, which is empty if the file doesn't exist yet.
+    pub fn load(filename: &str) -> io::Result<Self> {
+        let text = match fs::read_to_string(filename) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+        let mut attempts = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A history that can't be read can't protect against anything
+            let attempt = serde_json::from_str(line).map_err(|error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {error}", i + 1),
+                )
+            })?;
+            attempts.push(attempt);
+        }
+        Ok(History {
+            filename: filename.to_string(),
+            attempts,
+        })
+    }
+
+    /// Checks an answer against earlier attempts at the same part,
+    /// and at `now` against any wait the site asked for on the day.
+    pub fn check(
+        &self,
+        year: usize,
+        day: usize,
+        part: Part,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        let part = part.number();
+        let earlier: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.year == year && attempt.day == day)
+            .collect();
+
+        let mut low = None;
+        let mut high = None;
+        for attempt in earlier.iter().filter(|attempt| attempt.part == part) {
+            match attempt.outcome {
+                Outcome::Right => return Err(Refusal::AlreadyRight(attempt.answer.clone())),
+                Outcome::AlreadySolved => return Err(Refusal::AlreadySolved),
+                Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow if attempt.answer == answer => {
+                    return Err(Refusal::KnownWrong(attempt.outcome));
+                }
+                _ => {}
+            }
+            let Ok(value) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+            match attempt.outcome {
+                Outcome::TooLow => low = low.max(Some(value)),
+                Outcome::TooHigh => high = Some(high.map_or(value, |high: i128| high.min(value))),
+                _ => {}
+            }
+        }
+        // Only numbers have bounds
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(low) = low.filter(|&low| value <= low) {
+                return Err(Refusal::AtLeast(low + 1));
+            }
+            if let Some(high) = high.filter(|&high| value >= high) {
+                return Err(Refusal::AtMost(high - 1));
+            }
+        }
+        let until = earlier.iter().filter_map(|attempt| attempt.until).max();
+        match until.filter(|&until| until > now) {
+            Some(until) => Err(Refusal::Wait(Duration::from_secs(until - now))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn append(&mut self, attempt: Attempt) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.filename)?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyRight(answer) => write!(f, "{answer} was already accepted."),
+            Refusal::AlreadySolved => write!(f, "The part was already solved."),
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "It was already submitted and was {outcome}.")
+            }
+            Refusal::AtLeast(low) => write!(f, "Earlier answers show it's at least {low}."),
+            Refusal::AtMost(high) => write!(f, "Earlier answers show it's at most {high}."),
+            Refusal::Wait(wait) => {
+                write!(f, "The site asked to wait {} more.", describe_wait(*wait))
+            }
+        }
+    }
+}
+
+// As the site puts it, e.g. "4m 32s"
+pub fn describe_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs();
+    match seconds / 60 {
+        0 => format!("{seconds}s"),
+        minutes => format!("{minutes}m {}s", seconds % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(attempts: &[(&str, Outcome, Option<u64>)]) -> History {
+        let attempts = attempts
+            .iter()
+            .map(|&(answer, outcome, until)| Attempt {
+                year: 2024,
+                day: 7,
+                part: 1,
+                answer: answer.to_string(),
+                outcome,
+                time: 1000,
+                until,
+            })
+            .collect();
+        History {
+            filename: String::new(),
+            attempts,
+        }
+    }
+
+    fn check(history: &History, answer: &str, now: u64) -> Result<(), Refusal> {
+        history.check(2024, 7, Part::One, answer, now)
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let history = history(&[
+            ("abc", Outcome::Wrong, None),
+            ("50", Outcome::TooHigh, None),
+        ]);
+        assert!(matches!(
+            check(&history, "abc", 2000),
+            Err(Refusal::KnownWrong(Outcome::Wrong))
+        ));
+        assert!(matches!(
+            check(&history, "50", 2000),
+            Err(Refusal::KnownWrong(Outcome::TooHigh))
+        ));
+        assert!(check(&history, "abd", 2000).is_ok());
+        // Another part or day hasn't been tried
+        assert!(history.check(2024, 7, Part::Two, "abc", 2000).is_ok());
+        assert!(history.check(2024, 8, Part::One, "abc", 2000).is_ok());
+    }
+
+    #[test]
+    fn keeps_answers_within_bounds() {
+        let history = history(&[
+            ("10", Outcome::TooLow, None),
+            ("20", Outcome::TooLow, None),
+            ("90", Outcome::TooHigh, None),
+            ("80", Outcome::TooHigh, None),
+        ]);
+        assert!(matches!(
+            check(&history, "15", 2000),
+            Err(Refusal::AtLeast(21))
+        ));
+        assert!(matches!(
+            check(&history, "85", 2000),
+            Err(Refusal::AtMost(79))
+        ));
+        assert!(check(&history, "21", 2000).is_ok());
+        assert!(check(&history, "79", 2000).is_ok());
+        // Only numbers have bounds
+        assert!(check(&history, "x", 2000).is_ok());
+    }
+
+    #[test]
+    fn waits_until_the_site_allows_another_answer() {
+        let history = history(&[("1", Outcome::Wrong, Some(1060))]);
+        assert!(matches!(
+            check(&history, "2", 1000),
+            Err(Refusal::Wait(wait)) if wait == Duration::from_secs(60)
+        ));
+        assert!(check(&history, "2", 1060).is_ok());
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let history = history(&[("1", Outcome::Wrong, None), ("2", Outcome::Right, None)]);
+        assert!(matches!(
+            check(&history, "3", 2000),
+            Err(Refusal::AlreadyRight(answer)) if answer == "2"
+        ));
+    }
+}
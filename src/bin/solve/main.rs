@@ -0,0 +1,2113 @@
+use advent_of_code_2024::{bench::*, cache::*, export::*, manifest::*, observe::Observer, *};
+use client::{Client, ClientError, Outcome, DEFAULT_URL};
+use format::Format;
+use history::{describe_wait, Attempt, History, Refusal};
+use std::{
+    env, fmt, fs,
+    io::{self, IsTerminal, Read},
+    num::ParseIntError,
+    ops::Bound,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+// TODO: Replace with <u32 as FromStr>::Err when issue
+// https://github.com/rust-lang/rust/issues/85576
+// is fixed.
+type ParseDayError = ParseIntError;
+
+mod client;
+mod format;
+mod history;
+mod pool;
+mod serve;
+mod trace;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let command = Command::build(&args).unwrap_or_else(|err| {
+        println!("{err}");
+        process::exit(2);
+    });
+    // Keep structured output parseable by reporting the outcome on stderr
+    let structured = matches!(&command, Command::Solve(config) if config.format != Format::Text);
+    let result = match command {
+        Command::Solve(config) => run(config),
+        Command::List(year) => list(year),
+        Command::Verify {
+            year,
+            days,
+            manifest,
+        } => verify(year, days.as_deref(), manifest),
+        Command::Bench(config) => bench_days(config),
+        Command::Params { year, days } => list_params(year, &days),
+        Command::CacheList => list_cache(),
+        Command::CacheClear => clear_cache(),
+        Command::New { year, day } => new_day(year, day),
+        Command::Fetch { year, day } => fetch(year, day),
+        Command::Submit(config) => submit(config),
+        Command::Serve(config) => serve::serve(&config),
+    };
+    if let Err(err) = result {
+        match structured {
+            true => eprintln!("{err}"),
+            false => println!("{err}"),
+        }
+        process::exit(1);
+    }
+}
+
+const DEFAULT_PATTERN: &str = "inputs/{year}/day{day:02}.txt";
+const EXAMPLES_DIR: &str = "examples";
+const MANIFEST: &str = "answers.toml";
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_WARMUP: usize = 2;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 8080;
+// Puzzle inputs are tens of kilobytes
+const DEFAULT_MAX_BODY: usize = 1 << 20;
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const STDIN: &str = "-";
+const CACHE_DIR: &str = ".cache";
+// The skeleton for `new`, with DayNN standing for the day
+const DAY_TEMPLATE: &str = include_str!("day.rs.in");
+// Relative input patterns are resolved against this directory when set.
+const ROOT_VAR: &str = "AOC_DIR";
+// Sets the log level (info or debug) when -v isn't given.
+const LOG_VAR: &str = "AOC_LOG";
+// The session token for fetch, or else the first line of SESSION_FILE.
+const SESSION_VAR: &str = "AOC_SESSION";
+const SESSION_FILE: &str = ".session";
+// Every answer submit has sent, and what the site said
+const HISTORY_FILE: &str = ".submissions.jsonl";
+// Overrides client::DEFAULT_URL, e.g. to point fetch at a local server.
+const URL_VAR: &str = "AOC_URL";
+
+enum Command<'a> {
+    Solve(Config<'a>),
+    List(usize),
+    Verify {
+        // Everything in the manifest if neither is given
+        year: Option<usize>,
+        days: Option<Vec<usize>>,
+        manifest: &'a str,
+    },
+    Bench(BenchConfig<'a>),
+    Params {
+        year: usize,
+        days: Vec<usize>,
+    },
+    CacheList,
+    CacheClear,
+    New {
+        year: usize,
+        day: usize,
+    },
+    Fetch {
+        year: usize,
+        day: usize,
+    },
+    Submit(SubmitConfig<'a>),
+    Serve(ServeConfig<'a>),
+}
+
+struct Config<'a> {
+    year: usize,
+    days: Vec<usize>,
+    parts: &'static [Part],
+    filename: Option<&'a str>,
+    // From --inputs or --example; see `pattern` for the default
+    pattern: Option<String>,
+    batch: Option<&'a str>,
+    format: Format,
+    cx: Context,
+    jobs: usize,
+    export: Option<Arc<Exporter>>,
+    cache: Option<Cache>,
+}
+
+struct BenchConfig<'a> {
+    year: usize,
+    days: Vec<usize>,
+    parts: &'static [Part],
+    cx: Context,
+    jobs: usize,
+    warmup: usize,
+    runs: usize,
+    baseline: Option<&'a str>,
+    save: Option<&'a str>,
+    threshold: f64,
+}
+
+struct SubmitConfig<'a> {
+    year: usize,
+    day: usize,
+    part: Part,
+    filename: Option<&'a str>,
+    cx: Context,
+}
+
+struct ServeConfig<'a> {
+    host: &'a str,
+    port: u16,
+    jobs: usize,
+    max_body: usize,
+    timeout: Duration,
+}
+
+enum ConfigError<'a> {
+    WrongNumberOfParameters {
+        program_name: &'a str,
+        expected: usize,
+        actual: usize,
+    },
+    MissingValue(&'a str),
+    UnknownOption(&'a str),
+    InvalidPart(&'a str),
+    SubmitNeedsPart,
+    InvalidYear(&'a str),
+    InvalidFormat(&'a str),
+    InvalidExportFormat(&'a str),
+    InvalidLogLevel(String),
+    InvalidCacheCommand,
+    NeedsExport(&'a str),
+    InvalidParam(&'a str),
+    InvalidNumber {
+        option: &'a str,
+        value: &'a str,
+    },
+    ConflictingInputs,
+    FilenameWithManyDays,
+    BatchWithManyDays,
+    InvalidDay(DayError),
+}
+
+enum DayError {
+    OutOfRange {
+        low: usize,
+        high: usize,
+        actual: usize,
+    },
+    Unregistered {
+        year: usize,
+        day: usize,
+    },
+    NoneInRange {
+        year: usize,
+        start: usize,
+        end: usize,
+    },
+    ParseError(ParseDayError),
+}
+
+enum RunError {
+    FileError {
+        filename: String,
+        error: io::Error,
+    },
+    StdinError(io::Error),
+    SolveError {
+        filename: String,
+        input: String,
+        error: Box<SolveError>,
+    },
+    Panicked {
+        filename: String,
+        message: String,
+    },
+    EmptyBatch(String),
+    DayExists(String),
+    NoSession,
+    ClientError(ClientError),
+    NothingToSubmit(String),
+    SubmitRefused {
+        answer: String,
+        refusal: Refusal,
+    },
+    NotAccepted {
+        answer: String,
+        outcome: Outcome,
+    },
+    ServeError {
+        address: String,
+        error: String,
+    },
+    ManifestError {
+        filename: String,
+        error: ManifestError,
+    },
+    BaselineError {
+        filename: String,
+        error: BaselineError,
+    },
+    ExportError {
+        dir: String,
+        error: io::Error,
+    },
+    CacheError {
+        dir: String,
+        error: io::Error,
+    },
+    PartsFailed {
+        failed: usize,
+        total: usize,
+    },
+    DaysFailed {
+        failed: usize,
+        total: usize,
+    },
+    InputsFailed {
+        failed: usize,
+        total: usize,
+    },
+    VerifyFailed {
+        failed: usize,
+        total: usize,
+    },
+    Regressions {
+        count: usize,
+        threshold: f64,
+    },
+}
+
+impl fmt::Display for ConfigError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &ConfigError::WrongNumberOfParameters { program_name, expected, actual }
+                => write!(f, "Expected up to {expected} parameters. Found {actual}.\n{}", usage(program_name)),
+            ConfigError::MissingValue(option)
+                => write!(f, "Missing value for {option}."),
+            ConfigError::UnknownOption(option)
+                => write!(f, "Unknown option {option}."),
+            ConfigError::InvalidPart(part)
+                => write!(f, "Part must be 1 or 2. Found {part}."),
+            ConfigError::SubmitNeedsPart
+                => write!(f, "submit needs --part 1 or --part 2."),
+            ConfigError::InvalidYear(year)
+                => write!(f, "No days of {year} have a solver. Available years: {}.", describe_years()),
+            ConfigError::InvalidFormat(format)
+                => write!(f, "Format must be text, json, ndjson or csv. Found {format}."),
+            ConfigError::InvalidExportFormat(format)
+                => write!(f, "Export format must be pgm, ppm or cast. Found {format}."),
+            ConfigError::InvalidLogLevel(level)
+                => write!(f, "${LOG_VAR} must be info or debug. Found {level}."),
+            ConfigError::InvalidCacheCommand
+                => write!(f, "Expected cache list or cache clear."),
+            ConfigError::NeedsExport(option)
+                => write!(f, "{option} only applies with --export."),
+            ConfigError::InvalidParam(param)
+                => write!(f, "Expected --param name=value. Found {param}."),
+            &ConfigError::InvalidNumber { option, value }
+                => write!(f, "Invalid number for {option}: {value}."),
+            ConfigError::ConflictingInputs
+                => write!(f, "Only one of a filename, --inputs, --example and --batch can be given."),
+            ConfigError::FilenameWithManyDays
+                => write!(f, "A filename can only be given for a single day. Use --inputs to name each day's input."),
+            ConfigError::BatchWithManyDays
+                => write!(f, "--batch solves a single day."),
+            ConfigError::InvalidDay(day_error)
+                => day_error.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            &DayError::OutOfRange { low, high, actual } => {
+                write!(
+                    f,
+                    "Day must be between {low} and {high} (inclusive). Found {actual}."
+                )
+            }
+            &DayError::Unregistered { year, day } => {
+                write!(
+                    f,
+                    "Day {day} of {year} has no solver. Available days: {}.",
+                    describe_days(year)
+                )
+            }
+            &DayError::NoneInRange { year, start, end } => {
+                write!(
+                    f,
+                    "No days of {year} between {start} and {end} have a solver. Available days: {}.",
+                    describe_days(year)
+                )
+            }
+            DayError::ParseError(parse_int_error) => {
+                write!(f, "Invalid day: ")?;
+                parse_int_error.fmt(f)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::FileError { filename, error } => {
+                write!(f, "Error reading {filename}: ")?;
+                error.fmt(f)
+            }
+            RunError::StdinError(error) => {
+                write!(f, "Error reading stdin: ")?;
+                error.fmt(f)
+            }
+            RunError::SolveError {
+                filename,
+                input,
+                error,
+            } => write_diagnostic(f, filename, input, error),
+            RunError::ManifestError { filename, error } => {
+                write!(f, "Error in manifest {filename}: {error}")
+            }
+            RunError::BaselineError { filename, error } => {
+                write!(f, "Error in baseline {filename}: {error}")
+            }
+            RunError::ExportError { dir, error } => {
+                write!(f, "Error exporting frames to {dir}: {error}")
+            }
+            RunError::CacheError { dir, error } => write!(f, "Error in cache {dir}: {error}"),
+            RunError::Regressions { count, threshold } => {
+                write!(f, "{count} days regressed by more than {threshold}%.")
+            }
+            RunError::VerifyFailed { failed, total } => {
+                write!(f, "{failed} of {total} inputs failed verification.")
+            }
+            RunError::PartsFailed { failed, total } => {
+                write!(f, "{failed} of {total} parts failed.")
+            }
+            RunError::DaysFailed { failed, total } => write!(f, "{failed} of {total} days failed."),
+            RunError::InputsFailed { failed, total } => {
+                write!(f, "{failed} of {total} inputs failed.")
+            }
+            RunError::Panicked { filename, message } => {
+                write!(f, "Panicked solving {filename}: {message}")
+            }
+            RunError::EmptyBatch(dir) => write!(f, "No inputs in {dir}."),
+            RunError::DayExists(filename) => {
+                write!(f, "{filename} already exists. Not overwriting it.")
+            }
+            RunError::NoSession => write!(
+                f,
+                "No session token. Set ${SESSION_VAR} or put it in {SESSION_FILE}, \
+                 copying it from the session cookie of a logged in browser."
+            ),
+            RunError::ClientError(error) => error.fmt(f),
+            RunError::NothingToSubmit(reason) => {
+                write!(
+                    f,
+                    "Nothing to submit: the solver found no answer ({reason})."
+                )
+            }
+            RunError::SubmitRefused { answer, refusal } => {
+                write!(f, "Not submitting {answer}. {refusal}")
+            }
+            RunError::NotAccepted { answer, outcome } => {
+                write!(f, "{answer} was not accepted ({outcome}).")
+            }
+            RunError::ServeError { address, error } => {
+                write!(f, "Error listening on {address}: {error}")
+            }
+        }
+    }
+}
+
+impl From<DayError> for ConfigError<'_> {
+    fn from(value: DayError) -> Self {
+        ConfigError::InvalidDay(value)
+    }
+}
+
+impl From<ParseDayError> for DayError {
+    fn from(value: ParseDayError) -> Self {
+        DayError::ParseError(value)
+    }
+}
+
+// Points at the offending input, e.g.
+//   Day 5: invalid digit found in string
+//    --> input.txt:3:4
+//     |
+//   3 | 47|x3
+//     |    ^^
+fn write_diagnostic(
+    f: &mut fmt::Formatter<'_>,
+    filename: &str,
+    input: &str,
+    error: &SolveError,
+) -> fmt::Result {
+    let Some(location) = error.location() else {
+        return write!(f, "{error}");
+    };
+    let filename = match filename {
+        STDIN => "<stdin>",
+        _ => filename,
+    };
+    if let Some(day) = error.day {
+        write!(f, "Day {day}: ")?;
+    }
+    writeln!(f, "{}", error.kind)?;
+
+    let line = input.lines().nth(location.line - 1).unwrap_or_default();
+    let gutter = " ".repeat(location.line.to_string().len());
+    let indent = " ".repeat(location.column - 1);
+    let carets = "^".repeat(location.excerpt.chars().count().max(1));
+    writeln!(
+        f,
+        "{gutter}--> {filename}:{}:{}",
+        location.line, location.column
+    )?;
+    writeln!(f, "{gutter} |")?;
+    writeln!(f, "{} | {line}", location.line)?;
+    write!(f, "{gutter} | {indent}{carets}")
+}
+
+fn usage(program_name: &str) -> String {
+    format!(
+        "Usage: {program_name} days [filename] [--year year] [--part 1|2] [--inputs pattern | --example [n] | --batch dir] \
+         [--format text|json|ndjson|csv] [--param name=value]... [-j jobs] [--timeout time] [--no-cache] [-v | -vv] [--trace]\n\
+         \x20      [--export dir [--export-format pgm|ppm|cast] [--frames steps] [--stride n]]\n\
+         \x20      {program_name} list [--year year]\n\
+         \x20      {program_name} verify [days] [--year year] [--manifest file]\n\
+         \x20      {program_name} bench [days] [--year year] [--part 1|2] [--runs n] [--warmup n] \
+         [--baseline file] [--save file] [--threshold percent] [--param name=value]... [-j jobs] \
+         [--timeout time]\n\
+         \x20      {program_name} params [days] [--year year]\n\
+         \x20      {program_name} cache list|clear\n\
+         \x20      {program_name} new day [--year year]\n\
+         \x20      {program_name} fetch day [--year year]\n\
+         \x20      {program_name} submit day [filename] --part 1|2 [--year year] [--param name=value]... \
+         [--timeout time]\n\
+         \x20      {program_name} serve [--port port] [--host host] [-j jobs] [--max-body bytes] \
+         [--timeout time]\n\
+         days is a day (12), a range (3..=9 or 3..10), a list (1,5,12) or \"all\", \
+         of --year (default the latest year with a solver).\n\
+         A filename of - reads the input from stdin, as does omitting it for a single day \
+         without an input file when stdin is piped and neither --inputs nor --example is given.\n\
+         Otherwise, each day's input is found with the pattern, \
+         where {{year}} stands for the year and {{day}} and {{day:02}} for the day number \
+         (default {DEFAULT_PATTERN}). For {FIRST_YEAR}, a file missing from the year's directory \
+         is looked for without it.\n\
+         --example reads {EXAMPLES_DIR}/{{year}}/day12.txt instead, \
+         or {EXAMPLES_DIR}/{{year}}/day12-n.txt for example n.\n\
+         --batch solves the day with every file in dir and prints a row per input.\n\
+         --param overrides a puzzle parameter, such as a grid size that differs in the examples; \
+         params lists each day's parameters.\n\
+         -j solves that many days at once (default one per CPU, but one for bench \
+         so timings stay comparable). Output keeps the order of the days.\n\
+         --timeout (e.g. 30s, 500ms or 2m) stops a day that runs too long and reports it as failed.\n\
+         Answers are cached in {CACHE_DIR} by year, day, part, solver version, input and params; \
+         --no-cache solves them again, as do -v, --trace and --export. \
+         cache list shows the cached answers and cache clear removes them.\n\
+         -v logs what the solvers found along the way to stderr, and -vv logs more \
+         (or set ${LOG_VAR} to info or debug).\n\
+         --trace prints what the solvers report as they go, such as grids and step counts, \
+         to stderr.\n\
+         --export writes the grids solvers draw (days 6, 14 and 15) to dir as images (default ppm) \
+         or an asciicast recording per part, keeping the steps in --frames (e.g. 100..200) \
+         and then every --stride'th one.\n\
+         --format prints a record per day with answers, timings and errors for other tools.\n\
+         Relative paths are resolved against ${ROOT_VAR} if it is set.\n\
+         list shows which days have inputs and examples.\n\
+         fetch downloads a day's input to {DEFAULT_PATTERN} unless it's already there, \
+         using the session token in ${SESSION_VAR} or {SESSION_FILE} \
+         (and {DEFAULT_URL} or ${URL_VAR} as the site).\n\
+         submit solves a part and sends its answer to the site, recording each attempt in \
+         {HISTORY_FILE}. Answers already rejected, outside the bounds earlier answers set \
+         or sent before a wait the site asked for is over aren't sent.\n\
+         serve answers POST /{{year}}/day/{{day}} with the input as the body on --host \
+         (default {DEFAULT_HOST}) and --port (default {DEFAULT_PORT}), returning the same record \
+         as --format json. part=1 or 2 in the query picks a part and any other name=value \
+         is a param. Bodies over --max-body (default {DEFAULT_MAX_BODY} bytes) are refused \
+         and each request is answered within --timeout (default {}s), with a 504 \
+         if it's not solved by then. At most -j solves run at once, including ones that timed out \
+         but haven't stopped yet, and requests get a 503 while none can start.\n\
+         new creates src/yYYYY/dayNN.rs from a skeleton, an empty example and a manifest entry, \
+         without overwriting anything.\n\
+         verify checks answers against the manifest (default {MANIFEST}), where each [year.day] table \
+         (or [day] for {FIRST_YEAR}) \
+         maps an input name (input, example, example-n or a path) to its part1 and part2 answers.\n\
+         bench times each day's input (default {DEFAULT_RUNS} runs after {DEFAULT_WARMUP} warmup runs) \
+         and flags medians more than --threshold (default {DEFAULT_THRESHOLD}%) slower than the baseline.",
+        DEFAULT_REQUEST_TIMEOUT.as_secs()
+    )
+}
+
+impl Command<'_> {
+    fn build(args: &[String]) -> Result<Command<'_>, ConfigError<'_>> {
+        match args.get(1).map(String::as_str) {
+            Some("list") => match Self::split_year(&args[2..])? {
+                (positional, year) if positional.is_empty() => Ok(Command::List(year)),
+                (positional, _) => Err(ConfigError::WrongNumberOfParameters {
+                    program_name: &args[0],
+                    expected: 0,
+                    actual: positional.len(),
+                }),
+            },
+            Some("verify") => Self::build_verify(args),
+            Some("bench") => Self::build_bench(args),
+            Some("params") => match Self::split_year(&args[2..])? {
+                (positional, year) if positional.len() <= 1 => {
+                    let spec = positional.first().copied().unwrap_or("all");
+                    let days = Config::parse_days(year, spec)?;
+                    Ok(Command::Params { year, days })
+                }
+                (positional, _) => Err(ConfigError::WrongNumberOfParameters {
+                    program_name: &args[0],
+                    expected: 1,
+                    actual: positional.len(),
+                }),
+            },
+            Some("new") => {
+                let (year, day) = Self::build_puzzle(args)?;
+                Ok(Command::New { year, day })
+            }
+            Some("fetch") => {
+                let (year, day) = Self::build_puzzle(args)?;
+                Ok(Command::Fetch { year, day })
+            }
+            Some("submit") => Self::build_submit(args),
+            Some("serve") => Self::build_serve(args),
+            Some("cache") => match args.get(2).map(String::as_str) {
+                Some("list") if args.len() == 3 => Ok(Command::CacheList),
+                Some("clear") if args.len() == 3 => Ok(Command::CacheClear),
+                _ => Err(ConfigError::InvalidCacheCommand),
+            },
+            _ => Config::build(args).map(Command::Solve),
+        }
+    }
+
+    fn build_verify(args: &[String]) -> Result<Command<'_>, ConfigError<'_>> {
+        const EXPECTED: usize = 1;
+        let mut positional = Vec::new();
+        let mut manifest = MANIFEST;
+        let mut year = None;
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--manifest" => manifest = iter.next().ok_or(ConfigError::MissingValue(arg))?,
+                "--year" => {
+                    year = Some(parse_year(
+                        iter.next().ok_or(ConfigError::MissingValue(arg))?,
+                    )?);
+                }
+                option if option.starts_with("--") => {
+                    return Err(ConfigError::UnknownOption(option));
+                }
+                _ => positional.push(arg.as_str()),
+            }
+        }
+        if positional.len() > EXPECTED {
+            return Err(ConfigError::WrongNumberOfParameters {
+                program_name: &args[0],
+                expected: EXPECTED,
+                actual: positional.len(),
+            });
+        }
+        // Days are of the latest year unless another is given
+        if !positional.is_empty() {
+            year = Some(year.unwrap_or_else(default_year));
+        }
+        let days = positional
+            .first()
+            .map(|spec| Config::parse_days(year.unwrap_or_else(default_year), spec))
+            .transpose()?;
+        Ok(Command::Verify {
+            year,
+            days,
+            manifest,
+        })
+    }
+
+    fn build_bench(args: &[String]) -> Result<Command<'_>, ConfigError<'_>> {
+        const EXPECTED: usize = 1;
+        let mut positional = Vec::new();
+        let mut config = BenchConfig {
+            year: default_year(),
+            days: Vec::new(),
+            parts: &Part::BOTH,
+            cx: Context::default(),
+            jobs: 1,
+            warmup: DEFAULT_WARMUP,
+            runs: DEFAULT_RUNS,
+            baseline: None,
+            save: None,
+            threshold: DEFAULT_THRESHOLD,
+        };
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            let option = arg.as_str();
+            let mut value = || {
+                iter.next()
+                    .map(String::as_str)
+                    .ok_or(ConfigError::MissingValue(option))
+            };
+            let number = |value| ConfigError::InvalidNumber { option, value };
+            match option {
+                "--part" => config.parts = parse_part(value()?)?,
+                "--year" => config.year = parse_year(value()?)?,
+                "--param" => {
+                    let (name, value) = parse_param(value()?)?;
+                    config.cx = config.cx.with_param(name, value);
+                }
+                "--runs" => {
+                    let value = value()?;
+                    config.runs = value
+                        .parse()
+                        .ok()
+                        .filter(|&runs| runs > 0)
+                        .ok_or(number(value))?;
+                }
+                "-j" | "--jobs" => config.jobs = parse_jobs(option, value()?)?,
+                "--timeout" => {
+                    let timeout = parse_timeout(option, value()?)?;
+                    config.cx = config.cx.with_timeout(timeout);
+                }
+                "--warmup" => {
+                    let value = value()?;
+                    config.warmup = value.parse().map_err(|_| number(value))?;
+                }
+                "--threshold" => {
+                    let value = value()?;
+                    config.threshold = value.parse().map_err(|_| number(value))?;
+                }
+                "--baseline" => config.baseline = Some(value()?),
+                "--save" => config.save = Some(value()?),
+                option if option.starts_with("--") => {
+                    return Err(ConfigError::UnknownOption(option));
+                }
+                _ => positional.push(option),
+            }
+        }
+        if positional.len() > EXPECTED {
+            return Err(ConfigError::WrongNumberOfParameters {
+                program_name: &args[0],
+                expected: EXPECTED,
+                actual: positional.len(),
+            });
+        }
+        let spec = positional.first().copied().unwrap_or("all");
+        config.days = Config::parse_days(config.year, spec)?;
+        Ok(Command::Bench(config))
+    }
+
+    fn build_submit(args: &[String]) -> Result<Command<'_>, ConfigError<'_>> {
+        const EXPECTED: usize = 2;
+        let mut positional = Vec::new();
+        let mut year = default_year();
+        let mut part = None;
+        let mut cx = Context::default();
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            let option = arg.as_str();
+            let mut value = || {
+                iter.next()
+                    .map(String::as_str)
+                    .ok_or(ConfigError::MissingValue(option))
+            };
+            match option {
+                // One part at a time, so it's clear which answer is sent
+                "--part" => part = Some(parse_part(value()?)?[0]),
+                "--year" => year = parse_year(value()?)?,
+                "--param" => {
+                    let (name, value) = parse_param(value()?)?;
+                    cx = cx.with_param(name, value);
+                }
+                "--timeout" => cx = cx.with_timeout(parse_timeout(option, value()?)?),
+                option if option.starts_with("--") => {
+                    return Err(ConfigError::UnknownOption(option));
+                }
+                _ => positional.push(option),
+            }
+        }
+        if positional.is_empty() || positional.len() > EXPECTED {
+            return Err(ConfigError::WrongNumberOfParameters {
+                program_name: &args[0],
+                expected: EXPECTED,
+                actual: positional.len(),
+            });
+        }
+        Ok(Command::Submit(SubmitConfig {
+            year,
+            day: Config::parse_day(year, positional[0])?,
+            part: part.ok_or(ConfigError::SubmitNeedsPart)?,
+            filename: positional.get(1).copied(),
+            cx,
+        }))
+    }
+
+    fn build_serve(args: &[String]) -> Result<Command<'_>, ConfigError<'_>> {
+        let mut config = ServeConfig {
+            host: DEFAULT_HOST,
+            port: DEFAULT_PORT,
+            jobs: pool::default_jobs(),
+            max_body: DEFAULT_MAX_BODY,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+        };
+        let mut iter = args.iter().skip(2);
+        while let Some(arg) = iter.next() {
+            let option = arg.as_str();
+            let value = iter
+                .next()
+                .map(String::as_str)
+                .ok_or(ConfigError::MissingValue(option))?;
+            let number = || ConfigError::InvalidNumber { option, value };
+            match option {
+                "--host" => config.host = value,
+                "--port" => config.port = value.parse().map_err(|_| number())?,
+                "-j" | "--jobs" => config.jobs = parse_jobs(option, value)?,
+                "--max-body" => {
+                    config.max_body = value
+                        .parse()
+                        .ok()
+                        .filter(|&bytes| bytes > 0)
+                        .ok_or_else(number)?;
+                }
+                "--timeout" => config.timeout = parse_timeout(option, value)?,
+                _ => return Err(ConfigError::UnknownOption(option)),
+            }
+        }
+        Ok(Command::Serve(config))
+    }
+
+    // A day of any year, with or without a solver, for new and fetch.
+    fn build_puzzle(args: &[String]) -> Result<(usize, usize), ConfigError<'_>> {
+        let (positional, year) = Self::split_year_unchecked(&args[2..])?;
+        if positional.len() != 1 {
+            return Err(ConfigError::WrongNumberOfParameters {
+                program_name: &args[0],
+                expected: 1,
+                actual: positional.len(),
+            });
+        }
+        let day = positional[0].parse().map_err(DayError::from)?;
+        match day {
+            1..=25 => Ok((year, day)),
+            _ => Err(ConfigError::InvalidDay(DayError::OutOfRange {
+                low: 1,
+                high: 25,
+                actual: day,
+            })),
+        }
+    }
+
+    // Splits off --year for commands that take nothing else but positionals.
+    fn split_year(args: &[String]) -> Result<(Vec<&str>, usize), ConfigError<'_>> {
+        let (positional, year) = Self::split_year_unchecked(args)?;
+        match args.iter().position(|arg| arg == "--year") {
+            Some(i) if !years().any(|y| y == year) => Err(ConfigError::InvalidYear(&args[i + 1])),
+            _ => Ok((positional, year)),
+        }
+    }
+
+    // Like split_year, but allows years without any solvers yet, for new and fetch.
+    fn split_year_unchecked(args: &[String]) -> Result<(Vec<&str>, usize), ConfigError<'_>> {
+        let mut positional = Vec::new();
+        let mut year = default_year();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--year" => {
+                    let value = iter.next().ok_or(ConfigError::MissingValue(arg))?;
+                    year = value.parse().map_err(|_| ConfigError::InvalidYear(value))?;
+                }
+                option if option.starts_with("--") => {
+                    return Err(ConfigError::UnknownOption(option));
+                }
+                _ => positional.push(arg.as_str()),
+            }
+        }
+        Ok((positional, year))
+    }
+}
+
+impl Config<'_> {
+    fn pattern(&self) -> &str {
+        self.pattern.as_deref().unwrap_or(DEFAULT_PATTERN)
+    }
+
+    fn build(args: &[String]) -> Result<Config<'_>, ConfigError<'_>> {
+        const EXPECTED: usize = 2;
+        let program_name = if !args.is_empty() { &args[0] } else { "solve" };
+        let mut positional = Vec::new();
+        let mut pattern = None;
+        let mut year = default_year();
+        let mut parts: &[Part] = &Part::BOTH;
+        let mut format = Format::Text;
+        let mut cx = Context::default();
+        let mut jobs = pool::default_jobs();
+        let mut trace = false;
+        let mut log_level = None;
+        let mut no_cache = false;
+        let mut batch = None;
+        let mut export = None;
+        let mut export_format = ExportFormat::Ppm;
+        let mut frames = (Bound::Unbounded, Bound::Unbounded);
+        let mut stride = 1;
+        // The first option that only makes sense with --export
+        let mut export_option = None;
+        let mut args = args.iter().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            let set_pattern = |pattern: &mut Option<String>, value| match pattern {
+                Some(_) => Err(ConfigError::ConflictingInputs),
+                None => {
+                    *pattern = Some(value);
+                    Ok(())
+                }
+            };
+            match arg.as_str() {
+                "--inputs" => {
+                    let value = args.next().ok_or(ConfigError::MissingValue(arg))?;
+                    set_pattern(&mut pattern, value.clone())?;
+                }
+                "--example" => {
+                    let n = args.next_if(|n| n.parse::<usize>().is_ok());
+                    set_pattern(&mut pattern, example_pattern(n.map(String::as_str)))?;
+                }
+                "--part" => parts = parse_part(args.next().ok_or(ConfigError::MissingValue(arg))?)?,
+                "--year" => year = parse_year(args.next().ok_or(ConfigError::MissingValue(arg))?)?,
+                "--format" => {
+                    let value = args.next().ok_or(ConfigError::MissingValue(arg))?;
+                    format = Format::parse(value).ok_or(ConfigError::InvalidFormat(value))?;
+                }
+                "--param" => {
+                    let (name, value) =
+                        parse_param(args.next().ok_or(ConfigError::MissingValue(arg))?)?;
+                    cx = cx.with_param(name, value);
+                }
+                "-j" | "--jobs" => {
+                    jobs = parse_jobs(arg, args.next().ok_or(ConfigError::MissingValue(arg))?)?;
+                }
+                "--timeout" => {
+                    let value = args.next().ok_or(ConfigError::MissingValue(arg))?;
+                    cx = cx.with_timeout(parse_timeout(arg, value)?);
+                }
+                "--trace" => trace = true,
+                "--no-cache" => no_cache = true,
+                "--batch" => {
+                    batch = Some(args.next().ok_or(ConfigError::MissingValue(arg))?.as_str())
+                }
+                "-v" | "--verbose" => {
+                    log_level = match log_level {
+                        None => Some(LogLevel::Info),
+                        Some(_) => Some(LogLevel::Debug),
+                    }
+                }
+                "-vv" => log_level = Some(LogLevel::Debug),
+                "--export" => export = Some(args.next().ok_or(ConfigError::MissingValue(arg))?),
+                "--export-format" => {
+                    let value = args.next().ok_or(ConfigError::MissingValue(arg))?;
+                    export_format = ExportFormat::parse(value)
+                        .ok_or(ConfigError::InvalidExportFormat(value))?;
+                    export_option.get_or_insert(arg.as_str());
+                }
+                "--frames" => {
+                    frames = parse_frames(arg, args.next().ok_or(ConfigError::MissingValue(arg))?)?;
+                    export_option.get_or_insert(arg.as_str());
+                }
+                "--stride" => {
+                    let value = args.next().ok_or(ConfigError::MissingValue(arg))?;
+                    stride = value
+                        .parse()
+                        .ok()
+                        .filter(|&stride| stride > 0)
+                        .ok_or(ConfigError::InvalidNumber { option: arg, value })?;
+                    export_option.get_or_insert(arg.as_str());
+                }
+                option if option.starts_with("--") => {
+                    return Err(ConfigError::UnknownOption(option));
+                }
+                _ => positional.push(arg.as_str()),
+            }
+        }
+        if positional.is_empty() || positional.len() > EXPECTED {
+            return Err(ConfigError::WrongNumberOfParameters {
+                program_name,
+                expected: EXPECTED,
+                actual: positional.len(),
+            });
+        }
+
+        let days = Self::parse_days(year, positional[0])?;
+        let filename = positional.get(1).copied();
+        if filename.is_some() && days.len() > 1 {
+            return Err(ConfigError::FilenameWithManyDays);
+        }
+        if filename.is_some() && pattern.is_some() {
+            return Err(ConfigError::ConflictingInputs);
+        }
+        if batch.is_some() && (filename.is_some() || pattern.is_some()) {
+            return Err(ConfigError::ConflictingInputs);
+        }
+        if batch.is_some() && days.len() > 1 {
+            return Err(ConfigError::BatchWithManyDays);
+        }
+        if let (None, Some(option)) = (export, export_option) {
+            return Err(ConfigError::NeedsExport(option));
+        }
+
+        let log_level = log_level.map_or_else(log_level_from_env, |level| Ok(Some(level)))?;
+        if let Some(level) = log_level {
+            cx = cx.with_log_level(level);
+        }
+        // Cached answers would skip the solving these are there to show
+        let cache = match no_cache || trace || export.is_some() || log_level.is_some() {
+            true => None,
+            false => Some(Cache::new(resolve(CACHE_DIR.to_string()))),
+        };
+        let export = export.map(|dir| {
+            let exporter = Exporter::new(resolve(dir.to_string()), export_format);
+            Arc::new(exporter.frames(frames).stride(stride))
+        });
+        let mut observers: Vec<Arc<dyn Observer>> = Vec::new();
+        if trace {
+            observers.push(Arc::new(trace::Tracer));
+        }
+        if let Some(exporter) = &export {
+            observers.push(exporter.clone());
+        }
+        match observers.len() {
+            0 => {}
+            1 => cx = cx.with_observer(observers.remove(0)),
+            _ => cx = cx.with_observer(Arc::new(observers)),
+        }
+        Ok(Config {
+            year,
+            days,
+            parts,
+            filename,
+            pattern,
+            batch,
+            format,
+            cx,
+            jobs,
+            export,
+            cache,
+        })
+    }
+
+    fn parse_days(year: usize, spec: &str) -> Result<Vec<usize>, DayError> {
+        if spec == "all" {
+            return Ok(days(year).collect());
+        }
+        if let Some((start, end)) = spec.split_once("..") {
+            let start: usize = start.parse()?;
+            // The last day in the range
+            let end = match end.strip_prefix('=') {
+                Some(end) => end.parse()?,
+                None => end.parse::<usize>()?.saturating_sub(1),
+            };
+            // Puzzles only have days 1 to 25
+            if let Some(actual) = [start, end].into_iter().find(|day| !(1..=25).contains(day)) {
+                return Err(DayError::OutOfRange {
+                    low: 1,
+                    high: 25,
+                    actual,
+                });
+            }
+            let selected: Vec<usize> = days(year)
+                .filter(|day| (start..=end).contains(day))
+                .collect();
+            if selected.is_empty() {
+                return Err(DayError::NoneInRange { year, start, end });
+            }
+            return Ok(selected);
+        }
+        spec.split(',')
+            .map(|day| Self::parse_day(year, day))
+            .collect()
+    }
+
+    fn parse_day(year: usize, day: &str) -> Result<usize, DayError> {
+        let day: usize = day.parse()?;
+        if is_registered(year, day) {
+            return Ok(day);
+        }
+        let low = days(year).next().unwrap_or_default();
+        let high = days(year).last().unwrap_or_default();
+        if day < low || day > high {
+            return Err(DayError::OutOfRange {
+                low,
+                high,
+                actual: day,
+            });
+        }
+
+        Err(DayError::Unregistered { year, day })
+    }
+}
+
+fn parse_part(part: &str) -> Result<&'static [Part], ConfigError<'_>> {
+    match part {
+        "1" => Ok(&[Part::One]),
+        "2" => Ok(&[Part::Two]),
+        _ => Err(ConfigError::InvalidPart(part)),
+    }
+}
+
+fn parse_jobs<'a>(option: &'a str, value: &'a str) -> Result<usize, ConfigError<'a>> {
+    value
+        .parse()
+        .ok()
+        .filter(|&jobs| jobs > 0)
+        .ok_or(ConfigError::InvalidNumber { option, value })
+}
+
+// Seconds by default, e.g. 1.5, 30s, 500ms or 2m
+fn parse_timeout<'a>(option: &'a str, value: &'a str) -> Result<Duration, ConfigError<'a>> {
+    let (number, unit) = match value {
+        _ if value.ends_with("ms") => (&value[..value.len() - 2], 0.001),
+        _ if value.ends_with('s') => (&value[..value.len() - 1], 1.0),
+        _ if value.ends_with('m') => (&value[..value.len() - 1], 60.0),
+        _ => (value, 1.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * unit).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or(ConfigError::InvalidNumber { option, value })
+}
+
+fn log_level_from_env<'a>() -> Result<Option<LogLevel>, ConfigError<'a>> {
+    let Some(level) = env::var_os(LOG_VAR) else {
+        return Ok(None);
+    };
+    match level.to_string_lossy().as_ref() {
+        "" => Ok(None),
+        "info" => Ok(Some(LogLevel::Info)),
+        "debug" => Ok(Some(LogLevel::Debug)),
+        level => Err(ConfigError::InvalidLogLevel(level.to_string())),
+    }
+}
+
+// A single step (7) or a range of steps (100..200, 100..=200, 100.. or ..200)
+fn parse_frames<'a>(
+    option: &'a str,
+    value: &'a str,
+) -> Result<(Bound<u64>, Bound<u64>), ConfigError<'a>> {
+    let invalid = || ConfigError::InvalidNumber { option, value };
+    let parse = |step: &str| step.parse::<u64>().map_err(|_| invalid());
+    let Some((start, end)) = value.split_once("..") else {
+        let step = parse(value)?;
+        return Ok((Bound::Included(step), Bound::Included(step)));
+    };
+    let start = match start {
+        "" => Bound::Unbounded,
+        start => Bound::Included(parse(start)?),
+    };
+    let end = match end.strip_prefix('=') {
+        Some(end) => Bound::Included(parse(end)?),
+        None if end.is_empty() => Bound::Unbounded,
+        None => Bound::Excluded(parse(end)?),
+    };
+    Ok((start, end))
+}
+
+fn parse_param(param: &str) -> Result<(&str, &str), ConfigError<'_>> {
+    param
+        .split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or(ConfigError::InvalidParam(param))
+}
+
+fn parse_year(year: &str) -> Result<usize, ConfigError<'_>> {
+    year.parse()
+        .ok()
+        .filter(|&year| years().any(|y| y == year))
+        .ok_or(ConfigError::InvalidYear(year))
+}
+
+fn default_year() -> usize {
+    latest_year().unwrap_or(FIRST_YEAR)
+}
+
+fn describe_years() -> String {
+    years()
+        .map(|year| year.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Collapses the registered days into runs, e.g. "1-19, 21"
+fn describe_days(year: usize) -> String {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for day in days(year) {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => runs.push((day, day)),
+        }
+    }
+    runs.iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{start}-{end}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn input_path(pattern: &str, year: usize, day: usize) -> String {
+    let expand = |pattern: &str| {
+        resolve(
+            pattern
+                .replace("{year}", &year.to_string())
+                .replace("{day:02}", &format!("{day:02}"))
+                .replace("{day}", &day.to_string()),
+        )
+    };
+    let path = expand(pattern);
+    // FIRST_YEAR's files may be from before there were year directories
+    if year == FIRST_YEAR && pattern.contains("{year}/") && !Path::new(&path).exists() {
+        let undated = expand(&pattern.replace("{year}/", ""));
+        if Path::new(&undated).exists() {
+            return undated;
+        }
+    }
+    path
+}
+
+fn example_pattern(n: Option<&str>) -> String {
+    match n {
+        Some(n) => format!("{EXAMPLES_DIR}/{{year}}/day{{day:02}}-{n}.txt"),
+        None => format!("{EXAMPLES_DIR}/{{year}}/day{{day:02}}.txt"),
+    }
+}
+
+// "input" and "example[-n]" follow the usual layout; any other name is a pattern.
+fn manifest_input_path(year: usize, day: usize, name: &str) -> String {
+    match name {
+        "input" => input_path(DEFAULT_PATTERN, year, day),
+        "example" => input_path(&example_pattern(None), year, day),
+        _ => match name.strip_prefix("example-") {
+            Some(n) => input_path(&example_pattern(Some(n)), year, day),
+            None => input_path(name, year, day),
+        },
+    }
+}
+
+fn resolve(path: String) -> String {
+    match env::var_os(ROOT_VAR) {
+        Some(root) if Path::new(&path).is_relative() => {
+            Path::new(&root).join(path).display().to_string()
+        }
+        _ => path,
+    }
+}
+
+fn read_input(filename: &str) -> Result<String, RunError> {
+    if filename == STDIN {
+        let mut input = String::new();
+        return match io::stdin().read_to_string(&mut input) {
+            Ok(_) => Ok(input),
+            Err(error) => Err(RunError::StdinError(error)),
+        };
+    }
+    fs::read_to_string(filename).map_err(|error| RunError::FileError {
+        filename: filename.to_string(),
+        error,
+    })
+}
+
+fn solve_file(
+    year: usize,
+    day: usize,
+    filename: String,
+    parts: &[Part],
+    cx: &Context,
+    cache: Option<&Cache>,
+) -> Result<Solution, RunError> {
+    let input = read_input(&filename)?;
+    solve_input(year, day, filename, input, parts, cx, cache)
+}
+
+fn solve_input(
+    year: usize,
+    day: usize,
+    filename: String,
+    input: String,
+    parts: &[Part],
+    cx: &Context,
+    cache: Option<&Cache>,
+) -> Result<Solution, RunError> {
+    // One bad input shouldn't take down the rest of a run
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match cache {
+        Some(cache) => cache.solve(year, day, &input, parts, cx),
+        None => solve_with(year, day, &input, parts, cx),
+    }));
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => match payload.downcast_ref::<String>() {
+                    Some(message) => message.clone(),
+                    None => "unknown panic".to_string(),
+                },
+            };
+            return Err(RunError::Panicked { filename, message });
+        }
+    };
+    result.map_err(|error| RunError::SolveError {
+        filename,
+        input,
+        error: Box::new(error),
+    })
+}
+
+// Piped input stands in for a single day's input file when it doesn't exist and
+// no filename or pattern was given. Otherwise stdin is left alone, since it may
+// be meant for something else, such as a loop in a script calling this. An empty
+// stdin (e.g. /dev/null) falls back to the default pattern too.
+fn piped_input(config: &Config<'_>) -> Result<Option<String>, RunError> {
+    let [day] = config.days[..] else {
+        return Ok(None);
+    };
+    if config.filename.is_some()
+        || config.pattern.is_some()
+        || config.batch.is_some()
+        || Path::new(&input_path(config.pattern(), config.year, day)).exists()
+        || io::stdin().is_terminal()
+    {
+        return Ok(None);
+    }
+    let input = read_input(STDIN)?;
+    Ok(Some(input).filter(|input| !input.is_empty()))
+}
+
+fn run(config: Config<'_>) -> Result<(), RunError> {
+    let result = solve_days(&config);
+    let exported = finish_export(&config);
+    result.and(exported)
+}
+
+fn solve_days(config: &Config<'_>) -> Result<(), RunError> {
+    if config.format != Format::Text {
+        return format::run(config, config.format);
+    }
+    if let Some(dir) = config.batch {
+        return run_batch(config, dir);
+    }
+    match config.filename {
+        Some(filename) => {
+            let input = read_input(filename)?;
+            run_one(config, config.days[0], filename, input)
+        }
+        None if config.days.len() == 1 => {
+            if let Some(input) = piped_input(config)? {
+                return run_one(config, config.days[0], STDIN, input);
+            }
+            let filename = input_path(config.pattern(), config.year, config.days[0]);
+            let input = read_input(&filename)?;
+            run_one(config, config.days[0], &filename, input)
+        }
+        None => run_many(config),
+    }
+}
+
+fn finish_export(config: &Config<'_>) -> Result<(), RunError> {
+    let Some(exporter) = &config.export else {
+        return Ok(());
+    };
+    let dir = exporter.dir().display().to_string();
+    match exporter.finish() {
+        Ok(written) => {
+            eprintln!("Exported {written} frames to {dir}");
+            Ok(())
+        }
+        Err(error) => Err(RunError::ExportError { dir, error }),
+    }
+}
+
+fn list(year: usize) -> Result<(), RunError> {
+    let read_dir = |dir: String| match fs::read_dir(&dir) {
+        Ok(entries) => Ok(entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .collect::<Vec<_>>()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(RunError::FileError {
+            filename: dir,
+            error,
+        }),
+    };
+    let mut examples = read_dir(resolve(format!("{EXAMPLES_DIR}/{year}")))?;
+    // FIRST_YEAR's examples may be from before there were year directories
+    if year == FIRST_YEAR {
+        examples.extend(read_dir(resolve(EXAMPLES_DIR.to_string()))?);
+    }
+    // Shortest first, so day12.txt precedes day12-2.txt and day12-2.txt precedes day12-10.txt
+    examples.sort_by(|a: &String, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+    examples.dedup();
+
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Input".to_string(),
+        "Examples".to_string(),
+    ]];
+    let mut missing = Vec::new();
+    for day in days(year) {
+        let input = input_path(DEFAULT_PATTERN, year, day);
+        let found = Path::new(&input).is_file();
+        if !found {
+            missing.push(day.to_string());
+        }
+        let prefix = format!("day{day:02}");
+        let day_examples: Vec<&str> = examples
+            .iter()
+            .filter(|name| {
+                name.strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(".txt"))
+                    .is_some_and(|n| n.is_empty() || n.starts_with('-'))
+            })
+            .map(String::as_str)
+            .collect();
+        rows.push(vec![
+            day.to_string(),
+            match found {
+                true => input,
+                false => "missing".to_string(),
+            },
+            match day_examples.is_empty() {
+                true => "-".to_string(),
+                false => day_examples.join(", "),
+            },
+        ]);
+    }
+    print_table(&rows, usize::MAX);
+    if !missing.is_empty() {
+        println!("\nMissing inputs: {}", missing.join(", "));
+    }
+    Ok(())
+}
+
+fn verify(year: Option<usize>, days: Option<&[usize]>, manifest: &str) -> Result<(), RunError> {
+    let filename = resolve(manifest.to_string());
+    let text = read_input(&filename)?;
+    let manifest =
+        Manifest::parse(&text).map_err(|error| RunError::ManifestError { filename, error })?;
+
+    let mut rows = vec![vec![
+        "Year".to_string(),
+        "Day".to_string(),
+        "Input".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+    let mut problems = Vec::new();
+    let mut failed = 0;
+    let mut total = 0;
+    let selected = manifest.entries().filter(|(entry_year, day, _, _)| {
+        year.is_none_or(|year| year == *entry_year) && days.is_none_or(|days| days.contains(day))
+    });
+    for (year, day, name, expected) in selected {
+        total += 1;
+        let problems_before = problems.len();
+        let mut row = vec![year.to_string(), day.to_string(), name.to_string()];
+        let cx = expected
+            .params
+            .iter()
+            .fold(Context::default(), |cx, (name, value)| {
+                cx.with_param(name, value)
+            });
+        match solve_file(
+            year,
+            day,
+            manifest_input_path(year, day, name),
+            &expected.parts(),
+            &cx,
+            None,
+        ) {
+            Ok(solution) => {
+                let mut results: Vec<_> = solution.parts().collect();
+                for part in Part::BOTH {
+                    let Some(expected) = expected.get(part) else {
+                        row.push("-".to_string());
+                        continue;
+                    };
+                    let index = results.iter().position(|(p, _)| *p == part).unwrap();
+                    match results.swap_remove(index).1 {
+                        Ok(answer) if expected.matches(&answer) => row.push("ok".to_string()),
+                        Ok(answer) => {
+                            problems.push(format!(
+                                "Day {day} of {year} ({name}), part {part}: expected {expected}, got {answer}"
+                            ));
+                            row.push("FAIL".to_string());
+                        }
+                        Err(err) => {
+                            problems.push(format!("{err} ({name})"));
+                            row.push("error".to_string());
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                problems.push(err.to_string());
+                row.extend(Part::BOTH.map(|part| match expected.get(part) {
+                    Some(_) => "error".to_string(),
+                    None => "-".to_string(),
+                }));
+            }
+        }
+        if problems.len() > problems_before {
+            failed += 1;
+        }
+        rows.push(row);
+    }
+
+    print_table(&rows, usize::MAX);
+    for problem in &problems {
+        println!("\n{problem}");
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(RunError::VerifyFailed { failed, total }),
+    }
+}
+
+fn bench_days(config: BenchConfig<'_>) -> Result<(), RunError> {
+    let read_baseline = |filename: &str| -> Result<Baseline, RunError> {
+        let filename = resolve(filename.to_string());
+        let text = read_input(&filename)?;
+        Baseline::parse(&text).map_err(|error| RunError::BaselineError { filename, error })
+    };
+    let baseline = config.baseline.map(read_baseline).transpose()?;
+
+    let mut header = ["Day", "Runs", "Min", "Median", "Mean", "P95"]
+        .map(String::from)
+        .to_vec();
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"].map(String::from));
+    }
+    let mut rows = vec![header];
+    let mut errors = Vec::new();
+    let mut regressions = 0;
+    let mut saved = Baseline::default();
+    let bench_day = |&day: &usize| {
+        let filename = input_path(DEFAULT_PATTERN, config.year, day);
+        let stats = read_input(&filename).and_then(|input| {
+            bench(
+                config.year,
+                day,
+                &input,
+                config.parts,
+                &config.cx,
+                config.warmup,
+                config.runs,
+            )
+            .map_err(|error| RunError::SolveError {
+                filename,
+                input,
+                error: Box::new(error),
+            })
+        });
+        (day, stats)
+    };
+    pool::for_each_ordered(&config.days, config.jobs, bench_day, |(day, stats)| {
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(err) => {
+                errors.push(err.to_string());
+                return;
+            }
+        };
+        saved.insert(config.year, day, stats);
+
+        let mut row = vec![day.to_string(), stats.runs.to_string()];
+        row.extend([stats.min, stats.median, stats.mean, stats.p95].map(|d| format!("{d:.2?}")));
+        if let Some(baseline) = &baseline {
+            match baseline.get(config.year, day) {
+                Some(before) => {
+                    row.push(format!("{:.2?}", before.median));
+                    row.push(match stats.change(before) {
+                        Some(change) if change > config.threshold => {
+                            regressions += 1;
+                            format!("{change:+.1}% !")
+                        }
+                        Some(change) => format!("{change:+.1}%"),
+                        // A zero median can't be compared against
+                        None => "-".to_string(),
+                    });
+                }
+                None => row.extend(["-", "-"].map(String::from)),
+            }
+        }
+        rows.push(row);
+    });
+
+    print_table(&rows, 1);
+    for error in &errors {
+        println!("\n{error}");
+    }
+    if let Some(filename) = config.save {
+        let filename = resolve(filename.to_string());
+        let text = saved.to_toml().map_err(|error| RunError::BaselineError {
+            filename: filename.clone(),
+            error,
+        })?;
+        fs::write(&filename, text).map_err(|error| RunError::FileError { filename, error })?;
+    }
+    match (errors.len(), regressions) {
+        (0, 0) => Ok(()),
+        (0, count) => Err(RunError::Regressions {
+            count,
+            threshold: config.threshold,
+        }),
+        (failed, _) => Err(RunError::DaysFailed {
+            failed,
+            total: config.days.len(),
+        }),
+    }
+}
+
+fn list_params(year: usize, days: &[usize]) -> Result<(), RunError> {
+    let mut rows = vec![["Day", "Name", "Type", "Default", "Description"]
+        .map(String::from)
+        .to_vec()];
+    for &day in days {
+        for param in params(year, day).unwrap_or_default() {
+            let kind = match param.kind {
+                ParamKind::Integer => "integer",
+                ParamKind::Size => "size",
+            };
+            rows.push(vec![
+                day.to_string(),
+                param.name.to_string(),
+                kind.to_string(),
+                param.default.to_string(),
+                param.description.to_string(),
+            ]);
+        }
+    }
+    match rows.len() {
+        1 => println!("No parameters."),
+        _ => print_table(&rows, usize::MAX),
+    }
+    Ok(())
+}
+
+// Writes src/yYYYY/dayNN.rs, which the build picks up by itself,
+// along with an empty example and a manifest entry for its answers.
+fn new_day(year: usize, day: usize) -> Result<(), RunError> {
+    let year_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("y{year}"));
+    let module = year_dir.join(format!("day{day:02}.rs"));
+    if module.exists() || is_registered(year, day) {
+        return Err(RunError::DayExists(module.display().to_string()));
+    }
+    let write = |filename: &str, contents: &str| {
+        fs::write(filename, contents).map_err(|error| RunError::FileError {
+            filename: filename.to_string(),
+            error,
+        })
+    };
+    let create_dir = |dir: &Path| {
+        fs::create_dir_all(dir).map_err(|error| RunError::FileError {
+            filename: dir.display().to_string(),
+            error,
+        })
+    };
+    create_dir(&year_dir)?;
+    let source = DAY_TEMPLATE.replace("DayNN", &format!("Day{day:02}"));
+    write(&module.display().to_string(), &source)?;
+    println!("Created {}", module.display());
+
+    let example = input_path(&example_pattern(None), year, day);
+    match Path::new(&example).exists() {
+        true => println!("Kept {example}"),
+        false => {
+            create_dir(Path::new(&example).parent().unwrap_or(Path::new(".")))?;
+            write(&example, "")?;
+            println!("Created {example}");
+        }
+    }
+
+    let manifest = resolve(MANIFEST.to_string());
+    let text = match fs::read_to_string(&manifest) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            return Err(RunError::FileError {
+                filename: manifest,
+                error,
+            })
+        }
+    };
+    let parsed = Manifest::parse(&text).map_err(|error| RunError::ManifestError {
+        filename: manifest.clone(),
+        error,
+    })?;
+    match parsed.contains(year, day) {
+        true => println!("Kept [{year}.{day}] in {manifest}"),
+        false => {
+            let separator = match text.is_empty() || text.ends_with("\n\n") {
+                true => "",
+                false if text.ends_with('\n') => "\n",
+                false => "\n\n",
+            };
+            let entry = format!(
+                "{separator}[{year}.{day}]\n\
+                 # Fill in the answers once they're known, e.g. example = {{ part1 = 1, part2 = 2 }}\n\
+                 example = {{}}\n"
+            );
+            write(&manifest, &(text + &entry))?;
+            println!("Added [{year}.{day}] to {manifest}");
+        }
+    }
+    println!("Day {day} of {year} is registered the next time the crate is built.");
+    Ok(())
+}
+
+// Downloads the day's input to where solve looks for it, unless it's already there.
+fn fetch(year: usize, day: usize) -> Result<(), RunError> {
+    let filename = input_path(DEFAULT_PATTERN, year, day);
+    if Path::new(&filename).exists() {
+        println!("Kept {filename}");
+        return Ok(());
+    }
+    let input = client()?.input(year, day).map_err(RunError::ClientError)?;
+
+    let file_error = |filename: &str| {
+        let filename = filename.to_string();
+        move |error| RunError::FileError { filename, error }
+    };
+    if let Some(dir) = Path::new(&filename).parent() {
+        fs::create_dir_all(dir).map_err(file_error(&dir.display().to_string()))?;
+    }
+    // Written aside and renamed, so an interrupted fetch doesn't leave half an input
+    let partial = format!("{filename}.part");
+    fs::write(&partial, input).map_err(file_error(&partial))?;
+    fs::rename(&partial, &filename).map_err(file_error(&filename))?;
+    println!("Saved day {day} of {year} to {filename}");
+    Ok(())
+}
+
+// Solves one part and submits its answer, unless the history shows
+// it can't be right or the site asked to wait.
+fn submit(config: SubmitConfig<'_>) -> Result<(), RunError> {
+    let SubmitConfig {
+        year, day, part, ..
+    } = config;
+    let filename = match config.filename {
+        Some(filename) => filename.to_string(),
+        None => input_path(DEFAULT_PATTERN, year, day),
+    };
+    let input = read_input(&filename)?;
+    let cache = Cache::new(resolve(CACHE_DIR.to_string()));
+    let solution = solve_input(
+        year,
+        day,
+        filename.clone(),
+        input.clone(),
+        &[part],
+        &config.cx,
+        Some(&cache),
+    )?;
+    let answer = match solution.parts().next().map(|(_, result)| result) {
+        Some(Ok(Answer::Absent(reason))) => return Err(RunError::NothingToSubmit(reason)),
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(error)) => {
+            return Err(RunError::SolveError {
+                filename,
+                input,
+                error: Box::new(error),
+            })
+        }
+        None => unreachable!("the part was requested"),
+    };
+
+    let history_file = resolve(HISTORY_FILE.to_string());
+    let history_error = |error| RunError::FileError {
+        filename: history_file.clone(),
+        error,
+    };
+    let mut history = History::load(&history_file).map_err(history_error)?;
+    let now = unix_time();
+    if let Err(refusal) = history.check(year, day, part, &answer, now) {
+        return Err(RunError::SubmitRefused { answer, refusal });
+    }
+    println!("Submitting {answer} for day {day} of {year}, part {part}");
+    let verdict = client()?
+        .submit(year, day, part, &answer)
+        .map_err(RunError::ClientError)?;
+    history
+        .append(Attempt {
+            year,
+            day,
+            part: part.number(),
+            answer: answer.clone(),
+            outcome: verdict.outcome,
+            time: now,
+            until: verdict.wait.map(|wait| now + wait.as_secs()),
+        })
+        .map_err(history_error)?;
+
+    println!("{}", verdict.message);
+    if let Some(wait) = verdict.wait {
+        println!("Next answer for day {day} in {}.", describe_wait(wait));
+    }
+    match verdict.outcome {
+        Outcome::Right => Ok(()),
+        outcome => Err(RunError::NotAccepted { answer, outcome }),
+    }
+}
+
+fn client() -> Result<Client, RunError> {
+    let base_url = env::var(URL_VAR).unwrap_or_else(|_| DEFAULT_URL.to_string());
+    Ok(Client::new(&base_url, &session()?))
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn session() -> Result<String, RunError> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+    let filename = resolve(SESSION_FILE.to_string());
+    match fs::read_to_string(&filename) {
+        Ok(text) => match text.lines().next().map(str::trim) {
+            Some(session) if !session.is_empty() => Ok(session.to_string()),
+            _ => Err(RunError::NoSession),
+        },
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Err(RunError::NoSession),
+        Err(error) => Err(RunError::FileError { filename, error }),
+    }
+}
+
+fn list_cache() -> Result<(), RunError> {
+    let cache = Cache::new(resolve(CACHE_DIR.to_string()));
+    let dir = cache.dir().display().to_string();
+    let entries = cache.entries().map_err(|error| RunError::CacheError {
+        dir: dir.clone(),
+        error,
+    })?;
+    if entries.is_empty() {
+        println!("No cached answers in {dir}.");
+        return Ok(());
+    }
+    let mut rows = vec![[
+        "Year", "Day", "Part", "Version", "Input", "Params", "Answer", "",
+    ]
+    .map(String::from)
+    .to_vec()];
+    for entry in entries {
+        let key = &entry.key;
+        let params: Vec<_> = key
+            .params
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        rows.push(vec![
+            key.year.to_string(),
+            key.day.to_string(),
+            key.part.to_string(),
+            key.version.to_string(),
+            key.input.get(..12).unwrap_or(&key.input).to_string(),
+            params.join(" "),
+            entry.answer.to_string(),
+            match entry.is_stale() {
+                true => "stale".to_string(),
+                false => String::new(),
+            },
+        ]);
+    }
+    print_table(&rows, usize::MAX);
+    Ok(())
+}
+
+fn clear_cache() -> Result<(), RunError> {
+    let cache = Cache::new(resolve(CACHE_DIR.to_string()));
+    let dir = cache.dir().display().to_string();
+    let cleared = cache.clear().map_err(|error| RunError::CacheError {
+        dir: dir.clone(),
+        error,
+    })?;
+    println!("Removed {cleared} cached answers from {dir}.");
+    Ok(())
+}
+
+fn run_one(config: &Config<'_>, day: usize, filename: &str, input: String) -> Result<(), RunError> {
+    let parts = config.parts;
+    let cache = config.cache.as_ref();
+    let year = config.year;
+    let solution = solve_input(
+        year,
+        day,
+        filename.to_string(),
+        input,
+        parts,
+        &config.cx,
+        cache,
+    )?;
+    match filename {
+        STDIN => println!("Solving day {day} of {year} with stdin"),
+        _ => println!("Solving day {day} of {year} with {filename}"),
+    }
+    let timings = solution.timings;
+    let cached = solution.cached.clone();
+    let mut failed = 0;
+    for (part, result) in solution.parts() {
+        match result {
+            Ok(answer) if cached.contains(&part) => println!("Part {part}: {answer} (cached)"),
+            Ok(answer) => println!("Part {part}: {answer}"),
+            Err(err) => {
+                println!("Part {part} failed: {err}");
+                failed += 1;
+            }
+        }
+    }
+    if cached.len() == parts.len() {
+        println!("Time: none, all parts cached (--no-cache to solve again)");
+        return Ok(());
+    }
+    let mut phases = vec![
+        format!("parse {:.2?}", timings.parse),
+        format!("prepare {:.2?}", timings.prepare),
+    ];
+    for &part in parts {
+        if let Some(elapsed) = timings.part(part) {
+            phases.push(format!("part {part} {elapsed:.2?}"));
+        }
+    }
+    println!(
+        "Time: {} (total {:.2?})",
+        phases.join(", "),
+        timings.total()
+    );
+    match failed {
+        0 => Ok(()),
+        _ => Err(RunError::PartsFailed {
+            failed,
+            total: parts.len(),
+        }),
+    }
+}
+
+fn run_many(config: &Config<'_>) -> Result<(), RunError> {
+    let inputs: Vec<_> = config
+        .days
+        .iter()
+        .map(|&day| {
+            (
+                day.to_string(),
+                day,
+                input_path(config.pattern(), config.year, day),
+            )
+        })
+        .collect();
+    match print_results(config, "Day", &inputs) {
+        0 => Ok(()),
+        failed => Err(RunError::DaysFailed {
+            failed,
+            total: inputs.len(),
+        }),
+    }
+}
+
+fn run_batch(config: &Config<'_>, dir: &str) -> Result<(), RunError> {
+    let day = config.days[0];
+    let inputs: Vec<_> = batch_files(dir)?
+        .into_iter()
+        .map(|filename| {
+            let name = Path::new(&filename).file_name().unwrap_or_default();
+            (name.to_string_lossy().into_owned(), day, filename)
+        })
+        .collect();
+    println!(
+        "Solving day {day} of {} with {} inputs from {dir}",
+        config.year,
+        inputs.len()
+    );
+    match print_results(config, "Input", &inputs) {
+        0 => Ok(()),
+        failed => Err(RunError::InputsFailed {
+            failed,
+            total: inputs.len(),
+        }),
+    }
+}
+
+// Every file in the directory, by name. Hidden files are skipped.
+fn batch_files(dir: &str) -> Result<Vec<String>, RunError> {
+    let dir = resolve(dir.to_string());
+    let file_error = |error| RunError::FileError {
+        filename: dir.clone(),
+        error,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(file_error)? {
+        let entry = entry.map_err(file_error)?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.path().is_file() {
+            files.push(entry.path().display().to_string());
+        }
+    }
+    if files.is_empty() {
+        return Err(RunError::EmptyBatch(dir));
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Solves each (label, day, filename) and prints a row of answers and
+// timings per input, followed by any errors. Returns how many failed.
+fn print_results(config: &Config<'_>, label: &str, inputs: &[(String, usize, String)]) -> usize {
+    let parts = config.parts;
+    let mut header = vec![label.to_string()];
+    header.extend(parts.iter().map(|part| format!("Part {part}")));
+    header.extend(["Parse", "Prepare"].map(String::from));
+    header.extend(parts.iter().map(|part| format!("Time {part}")));
+    header.push("Total".to_string());
+    let mut rows = vec![header];
+    let mut errors = Vec::new();
+    let mut failed = 0;
+    let solve = |(label, day, filename): &(String, usize, String)| {
+        let cache = config.cache.as_ref();
+        let result = solve_file(
+            config.year,
+            *day,
+            filename.clone(),
+            parts,
+            &config.cx,
+            cache,
+        );
+        (label.clone(), result)
+    };
+    pool::for_each_ordered(inputs, config.jobs, solve, |(label, result)| {
+        let errors_before = errors.len();
+        let mut row = vec![label];
+        let mut timings = None;
+        let mut cached = Vec::new();
+        match result {
+            Ok(solution) => {
+                timings = Some(solution.timings);
+                cached = solution.cached.clone();
+                for (_, result) in solution.parts() {
+                    match result {
+                        Ok(answer) => row.push(answer.to_string()),
+                        Err(err) => {
+                            errors.push(err.to_string());
+                            row.push("failed".to_string());
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                let cell = match err {
+                    RunError::Panicked { .. } => "panicked",
+                    _ => "failed",
+                };
+                errors.push(err.to_string());
+                row.extend(parts.iter().map(|_| cell.to_string()));
+            }
+        }
+        if errors.len() > errors_before {
+            failed += 1;
+        }
+        match timings {
+            Some(_) if cached.len() == parts.len() => {
+                row.extend((0..parts.len() + 3).map(|_| "cached".to_string()))
+            }
+            Some(timings) => {
+                let phases = [Some(timings.parse), Some(timings.prepare)]
+                    .into_iter()
+                    .chain(parts.iter().map(|&part| match cached.contains(&part) {
+                        true => None,
+                        false => timings.part(part),
+                    }))
+                    .chain([Some(timings.total())]);
+                row.extend(phases.map(|elapsed| match elapsed {
+                    Some(elapsed) => format!("{elapsed:.2?}"),
+                    None => "cached".to_string(),
+                }));
+            }
+            None => row.extend((0..parts.len() + 3).map(|_| "-".to_string())),
+        }
+        rows.push(row);
+    });
+
+    print_table(&rows, 1 + parts.len());
+    for error in &errors {
+        println!("\n{error}");
+    }
+    failed
+}
+
+// Left-aligns the first `left` columns and right-aligns the rest.
+fn print_table(rows: &[Vec<String>], left: usize) {
+    let mut widths = Vec::new();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in rows {
+        let mut line = String::new();
+        for (i, (cell, &width)) in row.iter().zip(&widths).enumerate() {
+            match i < left {
+                true => line.push_str(&format!("{cell:<width$}  ")),
+                false => line.push_str(&format!("{cell:>width$}  ")),
+            }
+        }
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(filename: &str, input: &str, error: SolveError) -> String {
+        let error = RunError::SolveError {
+            filename: filename.to_string(),
+            input: input.to_string(),
+            error: Box::new(SolveError {
+                day: Some(2),
+                ..error
+            }),
+        };
+        error.to_string()
+    }
+
+    #[test]
+    fn points_at_the_offending_input() {
+        let input = "1 2\n3 x\n";
+        let error = SolveError::input(input, &input[6..7], "Invalid digit");
+        assert_eq!(
+            diagnostic("input.txt", input, error),
+            "Day 2: Invalid digit\n --> input.txt:2:3\n  |\n2 | 3 x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn counts_characters_rather_than_bytes() {
+        let input = "é€ xy";
+        let error = SolveError::input(input, &input[6..8], "Invalid digit");
+        assert_eq!(
+            diagnostic("input.txt", input, error),
+            "Day 2: Invalid digit\n --> input.txt:1:4\n  |\n1 | é€ xy\n  |    ^^"
+        );
+    }
+
+    #[test]
+    fn points_past_the_last_line_for_missing_input() {
+        let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10";
+        let error = SolveError::missing(input, "Expected more");
+        assert_eq!(
+            diagnostic("input.txt", input, error),
+            "Day 2: Expected more\n  --> input.txt:10:3\n   |\n10 | 10\n   |   ^"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+/// Caps how many tasks run at once, counting ones that nothing is
+/// waiting for any more but that haven't finished yet.
+pub struct Limit {
+    running: AtomicUsize,
+    max: usize,
+}
+
+/// A place taken in a `Limit`, given back when dropped.
+pub struct Slot(Arc<Limit>);
+
+/// The number of jobs to run at once when none is given.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+impl Limit {
+    pub fn new(max: usize) -> Arc<Self> {
+        Arc::new(Limit {
+            running: AtomicUsize::new(0),
+            max,
+        })
+    }
+
+    /// A slot for one more task, or `None` if `max` are already running.
+    pub fn try_acquire(self: &Arc<Self>) -> Option<Slot> {
+        self.running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.max).then_some(running + 1)
+            })
+            .ok()?;
+        Some(Slot(Arc::clone(self)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Runs `task` on every item using up to `jobs` threads and hands each
+/// result to `emit` in item order, as soon as it and all before it are done.
+/// With one job, everything runs on the calling thread.
+pub fn for_each_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    task: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R),
+) where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 || items.len() <= 1 {
+        items.iter().map(task).for_each(emit);
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, task) = (&next, &task);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                if sender.send((i, task(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&expected) {
+                emit(result);
+                expected += 1;
+            }
+        }
+    });
+}
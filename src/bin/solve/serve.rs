@@ -0,0 +1,206 @@
+use std::{
+    io::Read,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+};
+
+use advent_of_code_2024::*;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::{format::Record, pool::Limit, solve_input, DayError, RunError, ServeConfig};
+
+// What a record's input is called when it came in a request
+const BODY: &str = "request body";
+
+/// Answers `POST /{year}/day/{day}` with the day solved for the request body,
+/// as the same record `--format json` prints. `part` in the query picks a part
+/// and anything else in it is a parameter. Each worker handles one request at a time.
+///
+/// Every request is answered within the timeout. Solvers only stop early where
+/// they check their `Context`, so one that's still going when time is up is
+/// cancelled and left to finish on its own thread, while the worker moves on.
+/// Days that can run long on a large input check as they go, but the others
+/// can't be cancelled. Either way, a solve holds one of the `jobs` slots until
+/// it has stopped, and requests that find none free are turned away with a 503.
+pub fn serve(config: &ServeConfig<'_>) -> Result<(), RunError> {
+    let address = format!("{}:{}", config.host, config.port);
+    let server = Server::http(&address).map_err(|error| RunError::ServeError {
+        address: address.clone(),
+        error: error.to_string(),
+    })?;
+    println!("Listening on http://{address}");
+    let limit = Limit::new(config.jobs);
+    thread::scope(|scope| {
+        for _ in 0..config.jobs {
+            scope.spawn(|| {
+                while let Ok(request) = server.recv() {
+                    respond(config, &limit, request);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn respond(config: &ServeConfig<'_>, limit: &Arc<Limit>, mut request: Request) {
+    let (status, body) = handle(config, limit, &mut request);
+    eprintln!("{} {} {status}", request.method(), request.url());
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+    // The client may have gone, and there's no one else to tell
+    let _ = request.respond(response);
+}
+
+fn handle(config: &ServeConfig<'_>, limit: &Arc<Limit>, request: &mut Request) -> (u16, Value) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let Some((year, day)) = route(path) else {
+        let message = format!("No endpoint at {path}. Expected /{{year}}/day/{{day}}.");
+        return error(404, "not_found", message);
+    };
+    if *request.method() != Method::Post {
+        let message = "Expected a POST with the input as the body.";
+        return error(405, "method_not_allowed", message.to_string());
+    }
+    if !is_registered(year, day) {
+        let message = DayError::Unregistered { year, day }.to_string();
+        return error(404, "unknown_day", message);
+    }
+
+    let mut parts: &'static [Part] = &Part::BOTH;
+    let mut cx = Context::default().with_timeout(config.timeout);
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (name, value) = (decode(name), decode(value));
+        match name.as_str() {
+            "part" => match value.as_str() {
+                "1" => parts = &[Part::One],
+                "2" => parts = &[Part::Two],
+                _ => {
+                    let message = format!("Part must be 1 or 2. Found {value}.");
+                    return error(400, "bad_request", message);
+                }
+            },
+            _ => cx = cx.with_param(name, value),
+        }
+    }
+    let input = match read_body(request, config.max_body) {
+        Ok(input) => input,
+        Err(response) => return response,
+    };
+
+    // Only solves that timed out and are still going can fill every slot
+    let Some(slot) = limit.try_acquire() else {
+        let message = format!(
+            "All {} jobs are still finishing solves that timed out. Try again shortly.",
+            config.jobs
+        );
+        return error(503, "busy", message);
+    };
+    let cancel = CancelToken::new();
+    let cx = cx.with_cancel(cancel.clone());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        let result = solve_input(year, day, BODY.to_string(), input, parts, &cx, None);
+        // No one is waiting any more if the request timed out
+        let _ = sender.send(result);
+    });
+    let result = match receiver.recv_timeout(config.timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Err(RunError::SolveError {
+                filename: BODY.to_string(),
+                input: String::new(),
+                error: Box::new(SolveError::new(ErrorKind::TimedOut(config.timeout))),
+            })
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(RunError::Panicked {
+            filename: BODY.to_string(),
+            message: "the solver's thread stopped without an answer".to_string(),
+        }),
+    };
+    let record = Record::new(year, day, BODY, result);
+    let kinds = record.error_kinds();
+    let status = match () {
+        _ if kinds.is_empty() => 200,
+        _ if kinds.contains(&"timeout") => 504,
+        _ if kinds.contains(&"panic") => 500,
+        _ => 422,
+    };
+    (status, serde_json::to_value(&record).unwrap())
+}
+
+// "/2024/day/12" to (2024, 12)
+fn route(path: &str) -> Option<(usize, usize)> {
+    let rest = path.strip_prefix('/')?.trim_end_matches('/');
+    let (year, day) = rest.split_once("/day/")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+fn read_body(request: &mut Request, limit: usize) -> Result<String, (u16, Value)> {
+    let too_large = || {
+        let message = format!("The input is over the limit of {limit} bytes.");
+        error(413, "too_large", message)
+    };
+    if request.body_length().is_some_and(|length| length > limit) {
+        return Err(too_large());
+    }
+    // Without a length up front, read one byte past the limit to tell
+    let mut body = Vec::new();
+    let read = request
+        .as_reader()
+        .take(limit as u64 + 1)
+        .read_to_end(&mut body);
+    if let Err(err) = read {
+        return Err(error(
+            400,
+            "bad_request",
+            format!("Error reading the body: {err}"),
+        ));
+    }
+    if body.len() > limit {
+        return Err(too_large());
+    }
+    String::from_utf8(body)
+        .map_err(|_| error(400, "bad_request", "The input isn't UTF-8.".to_string()))
+}
+
+fn error(status: u16, kind: &str, message: String) -> (u16, Value) {
+    let body = json!({ "error": { "kind": kind, "message": message } });
+    (status, body)
+}
+
+// Undoes form encoding in a query, e.g. "7%2C11" to "7,11"
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let hex = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (byte, hex) {
+            (b'%', Some(decoded)) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            (b'+', _) => {
+                bytes.push(b' ');
+                rest = tail;
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -0,0 +1,28 @@
+use std::fmt::Write;
+
+use advent_of_code_2024::observe::*;
+use ndarray::Axis;
+
+/// Prints solver events to stderr, leaving stdout to the answers.
+pub struct Tracer;
+
+impl Observer for Tracer {
+    fn event(&self, origin: Origin, event: Event) {
+        let mut out = origin.to_string();
+        match event {
+            Event::Frame { step, grid } => {
+                writeln!(out, ", step {step}:").unwrap();
+                for row in grid.axis_iter(Axis(0)) {
+                    out.extend(row.iter());
+                    out.push('\n');
+                }
+            }
+            Event::Counter { name, value } => writeln!(out, ": {name}: {value}").unwrap(),
+            Event::Chosen { step, description } => {
+                writeln!(out, ", step {step}: {description}").unwrap()
+            }
+        }
+        // One write per event, so days solved in parallel don't interleave
+        eprint!("{out}");
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{params, solve_with, version, Answer, Context, LogLevel, Part, Solution, SolveResult};
+
+/// Answers from earlier runs, kept on disk as a JSON file per puzzle, part,
+/// input and parameters. An answer is only reused if the day's solver
+/// still has the version it was cached with.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// Everything a cached answer depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Key {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub version: u32,
+    /// SHA-256 of the input, in hex
+    pub input: String,
+    /// Every parameter the day declares, defaults included
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub key: Key,
+    pub answer: Answer,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Like `solve_with`, but takes each part's answer from the cache if it
+    /// has one and runs only the rest, caching any answers they find.
+    /// A cache that can't be read or written only costs the time it would
+    /// have saved; with logging on, failed writes are reported.
+    pub fn solve(
+        &self,
+        year: usize,
+        day: usize,
+        input: &str,
+        parts: &[Part],
+        cx: &Context,
+    ) -> SolveResult {
+        let (Some(version), Some(declared)) = (version(year, day), params(year, day)) else {
+            return solve_with(year, day, input, parts, cx);
+        };
+        let Ok(resolved) = cx.resolve(day, declared) else {
+            return solve_with(year, day, input, parts, cx);
+        };
+        let input_hash = hex(&Sha256::digest(input));
+        let params: BTreeMap<_, _> = resolved
+            .params()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        let key = |part: Part| Key {
+            year,
+            day,
+            part: part.number(),
+            version,
+            input: input_hash.clone(),
+            params: params.clone(),
+        };
+
+        let mut cached = Vec::new();
+        let mut uncached = Vec::new();
+        for &part in parts {
+            match self.get(&key(part)) {
+                Some(answer) => cached.push((part, answer)),
+                None => uncached.push(part),
+            }
+        }
+        let mut solution = match uncached.is_empty() {
+            true => Solution {
+                part1: None,
+                part2: None,
+                timings: Default::default(),
+                cached: Vec::new(),
+            },
+            false => solve_with(year, day, input, &uncached, cx)?,
+        };
+
+        for (part, result) in [(Part::One, &solution.part1), (Part::Two, &solution.part2)] {
+            if let Some(Ok(answer)) = result {
+                let entry = Entry {
+                    key: key(part),
+                    answer: answer.clone(),
+                };
+                if let Err(error) = self.insert(&entry) {
+                    cx.log(LogLevel::Info, || {
+                        format!("Couldn't cache {year} day {day}, part {part}: {error}")
+                    });
+                }
+            }
+        }
+        for (part, answer) in cached {
+            match part {
+                Part::One => solution.part1 = Some(Ok(answer)),
+                Part::Two => solution.part2 = Some(Ok(answer)),
+            }
+            solution.cached.push(part);
+        }
+        Ok(solution)
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        let text = fs::read_to_string(self.path(key)).ok()?;
+        let entry: Entry = serde_json::from_str(&text).ok()?;
+        (entry.key == *key).then_some(entry.answer)
+    }
+
+    pub fn insert(&self, entry: &Entry) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(entry)?;
+        fs::write(self.path(&entry.key), json + "\n")
+    }
+
+    /// Every cached answer, ordered by year, day and part.
+    /// Files that aren't cache entries are skipped.
+    pub fn entries(&self) -> io::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        for path in self.files()? {
+            let Ok(text) = fs::read_to_string(path) else {
+                continue;
+            };
+            if let Ok(entry) = serde_json::from_str::<Entry>(&text) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|entry| (entry.key.year, entry.key.day, entry.key.part));
+        Ok(entries)
+    }
+
+    /// Removes every cached answer and returns how many there were.
+    pub fn clear(&self) -> io::Result<usize> {
+        let files = self.files()?;
+        for path in &files {
+            fs::remove_file(path)?;
+        }
+        Ok(files.len())
+    }
+
+    fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            if name.ends_with(".json") {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    // The version isn't part of the name, so a new version's answer
+    // replaces the old one instead of piling up next to it.
+    fn path(&self, key: &Key) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(&key.input);
+        for (name, value) in &key.params {
+            hasher.update(format!("\n{name}={value}"));
+        }
+        let hash = hex(&hasher.finalize());
+        self.dir.join(format!(
+            "{}-day{:02}-part{}-{}.json",
+            key.year,
+            key.day,
+            key.part,
+            &hash[..16]
+        ))
+    }
+}
+
+impl Entry {
+    /// Whether the day's solver has changed since this answer was cached.
+    pub fn is_stale(&self) -> bool {
+        version(self.key.year, self.key.day) != Some(self.key.version)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    observe::{Event, Observer, Origin},
+    ErrorKind, Part, SolveError,
+};
+
+/// Everything a solver is given besides its input.
+#[derive(Clone, Default)]
+pub struct Context {
+    params: BTreeMap<String, String>,
+    timeout: Option<Duration>,
+    // Set from `timeout` when a run starts
+    deadline: Option<Instant>,
+    cancel: CancelToken,
+    observer: Option<Arc<dyn Observer>>,
+    log_level: Option<LogLevel>,
+    // Set as a run goes, to tell the observer where events come from
+    origin: Option<Origin>,
+}
+
+/// Stops a run from another thread. Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+/// A puzzle parameter a day declares, e.g. the size of day 18's memory space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// How much solvers say about what they did, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// A line or two per part, e.g. how many candidates were explored
+    Info,
+    /// Anything that helps follow a solver along, e.g. each candidate found
+    Debug,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// A non-negative integer, e.g. `1024`
+    Integer,
+    /// Two non-negative integers, e.g. `103x101`
+    Size,
+}
+
+impl Context {
+    /// Sets a parameter, e.g. `Context::default().with_param("bytes", "12")`.
+    /// Values are checked against the day's declarations when it runs.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Gives up on each run that takes longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Prints solvers' log messages up to `level` to stderr.
+    pub fn with_log_level(mut self, level: LogLevel) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    pub fn is_observed(&self) -> bool {
+        self.observer.is_some()
+    }
+
+    /// Reports an event to the observer. `event` is only called if there is
+    /// one, so solvers can build frames without slowing down normal runs.
+    pub fn observe(&self, event: impl FnOnce() -> Event) {
+        if let (Some(observer), Some(origin)) = (&self.observer, self.origin) {
+            observer.event(origin, event());
+        }
+    }
+
+    /// Writes a diagnostic to stderr if logging is on at `level`. Like
+    /// `observe`, `message` is only called when it will be printed.
+    pub fn log(&self, level: LogLevel, message: impl FnOnce() -> String) {
+        if self.log_level.is_some_and(|max| level <= max) {
+            match self.origin {
+                Some(origin) => eprintln!("{origin}: {}", message()),
+                None => eprintln!("{}", message()),
+            }
+        }
+    }
+
+    /// Fails once the run has been cancelled or has run out of time.
+    /// Solvers that can loop for a long time should call this regularly
+    /// and return its error.
+    pub fn check(&self) -> Result<(), SolveError> {
+        if self.cancel.is_cancelled() {
+            return Err(SolveError::new(ErrorKind::Cancelled));
+        }
+        match (self.timeout, self.deadline) {
+            (Some(timeout), Some(deadline)) if Instant::now() >= deadline => {
+                Err(SolveError::new(ErrorKind::TimedOut(timeout)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Checks the parameters against a day's declarations, fills in
+    /// defaults for the ones not given and starts the clock on the timeout.
+    pub(crate) fn resolve(&self, day: usize, declared: &[Param]) -> Result<Context, SolveError> {
+        for name in self.params.keys() {
+            if !declared.iter().any(|param| param.name == name) {
+                let message = match declared.len() {
+                    0 => "this day takes no parameters".to_string(),
+                    _ => format!(
+                        "expected one of {}",
+                        declared
+                            .iter()
+                            .map(|param| param.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                return Err(SolveError::param(name, message));
+            }
+        }
+        let mut resolved = self.clone();
+        for param in declared {
+            let value = resolved
+                .params
+                .entry(param.name.to_string())
+                .or_insert_with(|| param.default.to_string());
+            param
+                .kind
+                .check(value)
+                .map_err(|message| SolveError::param(param.name, message))?;
+        }
+        resolved.deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        resolved.origin = Some(Origin { day, part: None });
+        Ok(resolved)
+    }
+
+    pub(crate) fn in_part(&self, part: Part) -> Context {
+        let mut cx = self.clone();
+        cx.origin = cx.origin.map(|origin| Origin {
+            part: Some(part),
+            ..origin
+        });
+        cx
+    }
+
+    /// The value of a declared `Integer` parameter.
+    pub fn integer(&self, name: &str) -> usize {
+        parse_integer(self.declared(name)).expect("parameters are checked before solving")
+    }
+
+    /// The value of a declared `Size` parameter.
+    pub fn size(&self, name: &str) -> (usize, usize) {
+        parse_size(self.declared(name)).expect("parameters are checked before solving")
+    }
+
+    fn declared(&self, name: &str) -> &str {
+        match self.params.get(name) {
+            Some(value) => value,
+            None => panic!("parameter {name} isn't declared"),
+        }
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("params", &self.params)
+            .field("timeout", &self.timeout)
+            .field("deadline", &self.deadline)
+            .field("cancel", &self.cancel)
+            .field("observed", &self.is_observed())
+            .field("log_level", &self.log_level)
+            .field("origin", &self.origin)
+            .finish()
+    }
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl ParamKind {
+    fn check(self, value: &str) -> Result<(), String> {
+        match self {
+            ParamKind::Integer => parse_integer(value).map(|_| ()),
+            ParamKind::Size => parse_size(value).map(|_| ()),
+        }
+    }
+}
+
+fn parse_integer(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected an integer, found {value:?}"))
+}
+
+fn parse_size(value: &str) -> Result<(usize, usize), String> {
+    let size = value
+        .split_once('x')
+        .and_then(|(a, b)| Some((a.parse().ok()?, b.parse().ok()?)));
+    size.ok_or_else(|| format!("expected a size like 103x101, found {value:?}"))
+}
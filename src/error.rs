@@ -0,0 +1,220 @@
+use std::{error, fmt, time::Duration};
+
+use crate::Part;
+
+/// An error from solving a day, tagged with the day and part it came from.
+#[derive(Debug)]
+pub struct SolveError {
+    pub day: Option<usize>,
+    pub part: Option<Part>,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    UnknownDay,
+    /// The puzzle input could not be understood.
+    Input {
+        location: Location,
+        message: String,
+    },
+    /// A puzzle parameter was unknown or had the wrong type.
+    Param {
+        name: String,
+        message: String,
+    },
+    /// The run's `CancelToken` was cancelled.
+    Cancelled,
+    /// The run took longer than its timeout.
+    TimedOut(Duration),
+    Other(Box<dyn error::Error + Send + Sync>),
+}
+
+/// A position in the puzzle input. Line and column are 1-based,
+/// and the column counts characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, cut off at the end of its line.
+    pub excerpt: String,
+}
+
+impl SolveError {
+    pub fn new(kind: ErrorKind) -> Self {
+        SolveError {
+            day: None,
+            part: None,
+            kind,
+        }
+    }
+
+    /// An input error about `span`, which should be a slice of `input`.
+    pub fn input(input: &str, span: &str, message: impl fmt::Display) -> Self {
+        Self::new(ErrorKind::Input {
+            location: Location::of(input, span),
+            message: message.to_string(),
+        })
+    }
+
+    /// An input error about something missing from the end of `input`.
+    pub fn missing(input: &str, message: impl fmt::Display) -> Self {
+        let end = input.trim_end().len();
+        Self::input(input, &input[end..end], message)
+    }
+
+    pub(crate) fn param(name: &str, message: impl fmt::Display) -> Self {
+        Self::new(ErrorKind::Param {
+            name: name.to_string(),
+            message: message.to_string(),
+        })
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match &self.kind {
+            ErrorKind::Input { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn on_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    pub(crate) fn in_part(mut self, part: Part) -> Self {
+        self.part = Some(part);
+        self
+    }
+}
+
+impl Location {
+    /// Locates `span` within `input`. If `span` isn't actually a slice
+    /// of `input`, falls back to the first place its text occurs.
+    pub fn of(input: &str, span: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + span.len() <= input.len())
+            .or_else(|| input.find(span))
+            .unwrap_or_default();
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            excerpt: span.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Other(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.day, self.part) {
+            (Some(day), Some(part)) => write!(f, "Day {day}, part {part}: ")?,
+            (Some(day), None) => write!(f, "Day {day}: ")?,
+            _ => {}
+        }
+        self.kind.fmt(f)?;
+        if let Some(Location { line, column, .. }) = self.location() {
+            write!(f, " (line {line}, column {column})")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnknownDay => write!(f, "No solver registered"),
+            ErrorKind::Input { message, .. } => write!(f, "{message}"),
+            ErrorKind::Param { name, message } => write!(f, "Invalid parameter {name}: {message}"),
+            ErrorKind::Cancelled => write!(f, "Cancelled"),
+            ErrorKind::TimedOut(timeout) => write!(f, "Timed out after {timeout:?}"),
+            ErrorKind::Other(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<regex::Error> for SolveError {
+    fn from(value: regex::Error) -> Self {
+        SolveError::new(ErrorKind::Other(value.into()))
+    }
+}
+
+impl From<ndarray::ShapeError> for SolveError {
+    fn from(value: ndarray::ShapeError) -> Self {
+        SolveError::new(ErrorKind::Other(value.into()))
+    }
+}
+
+/// Attaches an input location to a failed parse.
+pub(crate) trait Locate<T> {
+    fn at(self, input: &str, span: &str) -> Result<T, SolveError>;
+}
+
+impl<T, E: fmt::Display> Locate<T> for Result<T, E> {
+    fn at(self, input: &str, span: &str) -> Result<T, SolveError> {
+        self.map_err(|err| SolveError::input(input, span, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_spans_by_line_and_character() {
+        let input = "ab\ncdé fg\n";
+        let location = Location::of(input, &input[8..10]);
+        assert_eq!((location.line, location.column), (2, 5));
+        assert_eq!(location.excerpt, "fg");
+    }
+
+    #[test]
+    fn locates_the_last_line_without_a_newline() {
+        let input = "12\n3x";
+        let location = Location::of(input, &input[4..]);
+        assert_eq!((location.line, location.column), (2, 2));
+        assert_eq!(location.excerpt, "x");
+    }
+
+    #[test]
+    fn cuts_excerpts_off_at_the_end_of_their_line() {
+        let input = "12\n34\n";
+        let location = Location::of(input, &input[1..5]);
+        assert_eq!((location.line, location.column), (1, 2));
+        assert_eq!(location.excerpt, "2");
+    }
+
+    #[test]
+    fn falls_back_to_searching_for_other_spans() {
+        let input = "12\n34\n";
+        let copy = "34".to_string();
+        let location = Location::of(input, &copy);
+        assert_eq!((location.line, location.column), (2, 1));
+        // Nowhere to be found
+        let location = Location::of(input, "56");
+        assert_eq!((location.line, location.column), (1, 1));
+    }
+
+    #[test]
+    fn puts_missing_things_at_the_end() {
+        let input = "12\n34\n\n";
+        let error = SolveError::missing(input, "Expected more");
+        let location = error.location().unwrap();
+        assert_eq!((location.line, location.column), (2, 3));
+        assert_eq!(location.excerpt, "");
+        assert_eq!(error.to_string(), "Expected more (line 2, column 3)");
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    ops::{Bound, RangeBounds},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use ndarray::{Array2, Axis};
+
+use crate::{
+    observe::{Event, Observer, Origin},
+    Part,
+};
+
+/// How long each frame shows for in an asciicast recording.
+const FRAME_SECONDS: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A greyscale image per frame
+    Pgm,
+    /// A colour image per frame
+    Ppm,
+    /// One asciicast v2 recording per part, playable with `asciinema play`
+    Asciicast,
+}
+
+/// Writes the frames solvers report to a directory, e.g.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use advent_of_code_2024::{export::*, *};
+/// let exporter = Arc::new(Exporter::new("frames", ExportFormat::Ppm).frames(100..200).stride(5));
+/// let cx = Context::default().with_observer(exporter.clone());
+/// solve_with(2024, 14, "p=0,4 v=3,-3", &[Part::Two], &cx.with_param("space", "7x11")).unwrap();
+/// exporter.finish().unwrap();
+/// ```
+///
+/// Images are named after the day, part and step, e.g. `day14-part2-000077.ppm`,
+/// and recordings after the day and part, e.g. `day14-part2.cast`.
+pub struct Exporter {
+    dir: PathBuf,
+    format: ExportFormat,
+    start: u64,
+    // Exclusive
+    end: Option<u64>,
+    stride: u64,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    written: usize,
+    recordings: BTreeMap<String, Recording>,
+    // Observers can't fail, so the first error waits here for `finish`
+    error: Option<io::Error>,
+}
+
+struct Recording {
+    out: BufWriter<File>,
+    frames: usize,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "pgm" => Some(ExportFormat::Pgm),
+            "ppm" => Some(ExportFormat::Ppm),
+            "cast" => Some(ExportFormat::Asciicast),
+            _ => None,
+        }
+    }
+}
+
+impl Exporter {
+    pub fn new(dir: impl Into<PathBuf>, format: ExportFormat) -> Self {
+        Exporter {
+            dir: dir.into(),
+            format,
+            start: 0,
+            end: None,
+            stride: 1,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Only exports frames whose step is in `steps`.
+    pub fn frames(mut self, steps: impl RangeBounds<u64>) -> Self {
+        self.start = match steps.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        self.end = match steps.end_bound() {
+            Bound::Included(&end) => Some(end.saturating_add(1)),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => None,
+        };
+        self
+    }
+
+    /// Only exports every `stride`th frame, counting from the start of the range.
+    pub fn stride(mut self, stride: u64) -> Self {
+        self.stride = stride.max(1);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn selects(&self, step: u64) -> bool {
+        step >= self.start
+            && self.end.is_none_or(|end| step < end)
+            && (step - self.start).is_multiple_of(self.stride)
+    }
+
+    /// Flushes any recordings and returns how many frames were written,
+    /// or the first error hit while writing them.
+    pub fn finish(&self) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(error) = state.error.take() {
+            return Err(error);
+        }
+        for recording in state.recordings.values_mut() {
+            recording.out.flush()?;
+        }
+        Ok(state.written)
+    }
+
+    fn write(
+        &self,
+        state: &mut State,
+        origin: Origin,
+        step: u64,
+        grid: &Array2<char>,
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let stem = stem(origin);
+        match self.format {
+            ExportFormat::Pgm | ExportFormat::Ppm => {
+                let (extension, image) = match self.format {
+                    ExportFormat::Pgm => ("pgm", pgm(grid)),
+                    _ => ("ppm", ppm(grid)),
+                };
+                let path = self.dir.join(format!("{stem}-{step:06}.{extension}"));
+                fs::write(path, image)?;
+            }
+            ExportFormat::Asciicast => {
+                if !state.recordings.contains_key(&stem) {
+                    let file = File::create(self.dir.join(format!("{stem}.cast")))?;
+                    let mut out = BufWriter::new(file);
+                    let (height, width) = grid.dim();
+                    let header =
+                        serde_json::json!({ "version": 2, "width": width, "height": height });
+                    writeln!(out, "{header}")?;
+                    state
+                        .recordings
+                        .insert(stem.clone(), Recording { out, frames: 0 });
+                }
+                let recording = state.recordings.get_mut(&stem).unwrap();
+                // Clear the screen and draw the grid from the top left
+                let mut text = String::from("\x1b[H\x1b[2J");
+                let rows: Vec<String> = grid
+                    .axis_iter(Axis(0))
+                    .map(|row| row.iter().collect())
+                    .collect();
+                text.push_str(&rows.join("\r\n"));
+                let time = recording.frames as f64 * FRAME_SECONDS;
+                writeln!(recording.out, "{}", serde_json::json!([time, "o", text]))?;
+                recording.frames += 1;
+            }
+        }
+        state.written += 1;
+        Ok(())
+    }
+}
+
+impl Observer for Exporter {
+    fn event(&self, origin: Origin, event: Event) {
+        let Event::Frame { step, grid } = event else {
+            return;
+        };
+        if !self.selects(step) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if state.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.write(&mut state, origin, step, &grid) {
+            state.error = Some(error);
+        }
+    }
+}
+
+fn stem(origin: Origin) -> String {
+    match origin.part {
+        Some(Part::One) => format!("day{:02}-part1", origin.day),
+        Some(Part::Two) => format!("day{:02}-part2", origin.day),
+        None => format!("day{:02}", origin.day),
+    }
+}
+
+// One pixel per cell
+fn pgm(grid: &Array2<char>) -> Vec<u8> {
+    let (height, width) = grid.dim();
+    let mut image = format!("P5\n{width} {height}\n255\n").into_bytes();
+    image.extend(grid.iter().map(|&c| {
+        let [r, g, b] = colour(c).map(f64::from);
+        (0.299 * r + 0.587 * g + 0.114 * b).round() as u8
+    }));
+    image
+}
+
+fn ppm(grid: &Array2<char>) -> Vec<u8> {
+    let (height, width) = grid.dim();
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    image.extend(grid.iter().flat_map(|&c| colour(c)));
+    image
+}
+
+fn colour(cell: char) -> [u8; 3] {
+    match cell {
+        ' ' | '.' => [255, 255, 255],
+        '#' => [40, 40, 40],
+        // Boxes
+        'O' | '[' | ']' => [180, 120, 60],
+        // Robots and guards
+        '@' | '^' | '>' | 'v' | '<' => [220, 40, 40],
+        // Visited cells and day 14's robots
+        'X' => [40, 160, 60],
+        _ => [90, 90, 200],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use ndarray::array;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    // A fresh directory for one test's frames
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-export-{test}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn send(exporter: &Exporter, steps: impl IntoIterator<Item = u64>) {
+        let origin = Origin {
+            day: 14,
+            part: Some(Part::Two),
+        };
+        for step in steps {
+            let grid = array![['#', '.', '@'], ['O', 'X', '.']];
+            exporter.event(origin, Event::Frame { step, grid });
+        }
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut files: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn exports_frames_in_range() {
+        let dir = temp_dir("range");
+        let exporter = Exporter::new(&dir, ExportFormat::Pgm).frames(3..=5);
+        send(&exporter, 0..10);
+        assert_eq!(exporter.finish().unwrap(), 3);
+        assert_eq!(
+            files(&dir),
+            [
+                "day14-part2-000003.pgm",
+                "day14-part2-000004.pgm",
+                "day14-part2-000005.pgm"
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn strides_from_the_start_of_the_range() {
+        let dir = temp_dir("stride");
+        let exporter = Exporter::new(&dir, ExportFormat::Ppm).frames(2..).stride(3);
+        send(&exporter, 0..10);
+        assert_eq!(exporter.finish().unwrap(), 3);
+        assert_eq!(
+            files(&dir),
+            [
+                "day14-part2-000002.ppm",
+                "day14-part2-000005.ppm",
+                "day14-part2-000008.ppm"
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn writes_a_pixel_per_cell() {
+        let dir = temp_dir("images");
+        for format in [ExportFormat::Pgm, ExportFormat::Ppm] {
+            let exporter = Exporter::new(&dir, format);
+            send(&exporter, [0]);
+            exporter.finish().unwrap();
+        }
+
+        let pgm = fs::read(dir.join("day14-part2-000000.pgm")).unwrap();
+        let header = b"P5\n3 2\n255\n";
+        assert_eq!(&pgm[..header.len()], header);
+        assert_eq!(&pgm[header.len()..], [40, 255, 94, 131, 113, 255]);
+
+        let ppm = fs::read(dir.join("day14-part2-000000.ppm")).unwrap();
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 3 * 6);
+        assert_eq!(
+            &ppm[header.len()..header.len() + 9],
+            [40, 40, 40, 255, 255, 255, 220, 40, 40]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn records_asciicast_events() {
+        let dir = temp_dir("cast");
+        let exporter = Exporter::new(&dir, ExportFormat::Asciicast);
+        send(&exporter, 0..2);
+        assert_eq!(exporter.finish().unwrap(), 2);
+        assert_eq!(files(&dir), ["day14-part2.cast"]);
+
+        let text = fs::read_to_string(dir.join("day14-part2.cast")).unwrap();
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], json!({ "version": 2, "width": 3, "height": 2 }));
+        assert_eq!(lines[1], json!([0.0, "o", "\x1b[H\x1b[2J#.@\r\nOX."]));
+        assert_eq!(lines[2][0], json!(0.1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,76 +1,252 @@
-use crate::days::*;
 use core::fmt;
-use std::error::{self};
+use std::time::{Duration, Instant};
 
-pub type SolveError = Box<dyn error::Error>;
-pub type SolveResult = Result<(String, String), SolveError>;
+pub use answer::Answer;
+pub use context::{CancelToken, Context, LogLevel, Param, ParamKind};
+pub use error::{ErrorKind, Location, SolveError};
 
+/// The year this crate started with. Inputs, examples and manifest entries
+/// from before it held other years have no year and belong to this one.
+pub const FIRST_YEAR: usize = 2024;
+
+pub type SolveResult = Result<Solution, SolveError>;
+pub type PartResult = Result<Answer, SolveError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    /// 1 or 2, as the puzzle numbers it.
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The requested parts of a parsed puzzle, or `None` for parts
+/// that weren't requested. Each part succeeds or fails on its own.
 #[derive(Debug)]
-enum Error<'a> {
-    InputError(&'a str),
+pub struct Solution {
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+    pub timings: Timings,
+    /// Parts answered from a `Cache` rather than run. They have no timings.
+    pub cached: Vec<Part>,
+}
+
+/// How long each phase of a run took. Parts that weren't run are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub prepare: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Solution {
+    /// The parts that were run, in order.
+    pub fn parts(self) -> impl Iterator<Item = (Part, PartResult)> {
+        [(Part::One, self.part1), (Part::Two, self.part2)]
+            .into_iter()
+            .filter_map(|(part, result)| Some((part, result?)))
+    }
 }
 
-impl error::Error for Error<'_> {}
+impl Timings {
+    pub fn part(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.prepare + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
 
-impl fmt::Display for Error<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InputError(msg) => write!(f, "{msg}"),
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
         }
     }
 }
 
-pub fn solve(day: usize, input: &str) -> SolveResult {
-    DAY_FNS[day - 1](input)
+/// A single day's puzzle, split into parsing and the two parts.
+///
+/// `parse` does all the work that both parts share, so the parsed
+/// `Input` can be handed to either part (or both) without redoing it.
+pub trait Solver {
+    type Input<'a>;
+
+    /// Parameters that aren't part of the puzzle input, such as a grid size
+    /// that differs between the example and the real puzzle. Their values
+    /// are available from the `Context`, checked and with defaults filled in.
+    const PARAMS: &'static [Param] = &[];
+
+    /// Bump this whenever a change could give different answers,
+    /// so answers cached from earlier versions aren't used.
+    const VERSION: u32 = 1;
+
+    fn parse<'a>(input: &'a str, cx: &Context) -> Result<Self::Input<'a>, SolveError>;
+
+    /// Work both parts share that's worth timing apart from parsing,
+    /// e.g. walking a maze once so each part can read off its answer.
+    fn prepare(_input: &mut Self::Input<'_>, _cx: &Context) -> Result<(), SolveError> {
+        Ok(())
+    }
+
+    fn part1(input: &Self::Input<'_>, cx: &Context) -> PartResult;
+    fn part2(input: &Self::Input<'_>, cx: &Context) -> PartResult;
+}
+
+pub fn solve(year: usize, day: usize, input: &str) -> SolveResult {
+    solve_parts(year, day, input, &Part::BOTH)
+}
+
+/// Parses the input once and runs only the given parts,
+/// so an expensive part can be skipped entirely.
+pub fn solve_parts(year: usize, day: usize, input: &str, parts: &[Part]) -> SolveResult {
+    solve_with(year, day, input, parts, &Context::default())
 }
 
+/// Like `solve_parts`, with parameters and the rest of the context
+/// given explicitly.
+pub fn solve_with(
+    year: usize,
+    day: usize,
+    input: &str,
+    parts: &[Part],
+    cx: &Context,
+) -> SolveResult {
+    let solver =
+        solver(year, day).ok_or_else(|| SolveError::new(ErrorKind::UnknownDay).on_day(day))?;
+    solver.run(day, input, parts, cx)
+}
+
+/// The parameters a day declares, or `None` if it has no solver.
+pub fn params(year: usize, day: usize) -> Option<&'static [Param]> {
+    solver(year, day).map(|solver| solver.params())
+}
+
+/// The version of a day's solver, or `None` if it has no solver.
+pub fn version(year: usize, day: usize) -> Option<u32> {
+    solver(year, day).map(|solver| solver.version())
+}
+
+/// Every year with at least one solver, in ascending order.
+pub fn years() -> impl Iterator<Item = usize> {
+    let mut years: Vec<usize> = years::REGISTRY.iter().map(|(year, _, _)| *year).collect();
+    years.dedup();
+    years.into_iter()
+}
+
+/// The most recent year with a solver, which is the one to use by default.
+pub fn latest_year() -> Option<usize> {
+    years().last()
+}
+
+/// Every day of `year` with a registered solver, in ascending order.
+/// There may be gaps, e.g. if day 21 is solved before day 20.
+pub fn days(year: usize) -> impl Iterator<Item = usize> {
+    years::REGISTRY
+        .iter()
+        .filter(move |(y, _, _)| *y == year)
+        .map(|(_, day, _)| *day)
+}
+
+pub fn is_registered(year: usize, day: usize) -> bool {
+    solver(year, day).is_some()
+}
+
+fn solver(year: usize, day: usize) -> Option<&'static dyn Run> {
+    years::REGISTRY
+        .iter()
+        .find(|(y, d, _)| (*y, *d) == (year, day))
+        .map(|(_, _, solver)| *solver)
+}
+
+/// Object-safe counterpart of `Solver`, so the registry can hold every day.
+trait Run: Sync {
+    fn run(&self, day: usize, input: &str, parts: &[Part], cx: &Context) -> SolveResult;
+    fn params(&self) -> &'static [Param];
+    fn version(&self) -> u32;
+}
+
+impl<S: Solver + Sync> Run for S {
+    fn run(&self, day: usize, input: &str, parts: &[Part], cx: &Context) -> SolveResult {
+        let cx = &cx.resolve(day, S::PARAMS).map_err(|err| err.on_day(day))?;
+        let mut timings = Timings::default();
+        let start = Instant::now();
+        let mut input = S::parse(input, cx).map_err(|err| err.on_day(day))?;
+        timings.parse = start.elapsed();
+        let start = Instant::now();
+        S::prepare(&mut input, cx).map_err(|err| err.on_day(day))?;
+        timings.prepare = start.elapsed();
+
+        let run_part = |part| {
+            let start = Instant::now();
+            let cx = &cx.in_part(part);
+            let result = match part {
+                Part::One => S::part1(&input, cx),
+                Part::Two => S::part2(&input, cx),
+            };
+            let elapsed = start.elapsed();
+            (result.map_err(|err| err.on_day(day).in_part(part)), elapsed)
+        };
+        let mut solution = Solution {
+            part1: None,
+            part2: None,
+            timings,
+            cached: Vec::new(),
+        };
+        if parts.contains(&Part::One) {
+            let (result, elapsed) = run_part(Part::One);
+            solution.part1 = Some(result);
+            solution.timings.part1 = Some(elapsed);
+        }
+        if parts.contains(&Part::Two) {
+            let (result, elapsed) = run_part(Part::Two);
+            solution.part2 = Some(result);
+            solution.timings.part2 = Some(elapsed);
+        }
+        Ok(solution)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn version(&self) -> u32 {
+        S::VERSION
+    }
+}
+
+mod answer;
+pub mod bench;
+pub mod cache;
+mod context;
+mod error;
+pub mod export;
+pub mod manifest;
+pub mod observe;
+
 mod util {
     pub mod grid;
+    pub mod parse;
 }
 
-// TODO: Generate via procedural macro
-pub const DAYS: usize = 19;
-
-mod days {
-    pub mod day01;
-    pub mod day02;
-    pub mod day03;
-    pub mod day04;
-    pub mod day05;
-    pub mod day06;
-    pub mod day07;
-    pub mod day08;
-    pub mod day09;
-    pub mod day10;
-    pub mod day11;
-    pub mod day12;
-    pub mod day13;
-    pub mod day14;
-    pub mod day15;
-    pub mod day16;
-    pub mod day17;
-    pub mod day18;
-    pub mod day19;
-}
-
-const DAY_FNS: [fn(&str) -> SolveResult; DAYS] = [
-    day01::solve,
-    day02::solve,
-    day03::solve,
-    day04::solve,
-    day05::solve,
-    day06::solve,
-    day07::solve,
-    day08::solve,
-    day09::solve,
-    day10::solve,
-    day11::solve,
-    day12::solve,
-    day13::solve,
-    day14::solve,
-    day15::solve,
-    day16::solve,
-    day17::solve,
-    day18::solve,
-    day19::solve,
-];
+// Module declarations and REGISTRY are generated by build.rs
+// from the files present in src/yYYYY, e.g. src/y2024/day01.rs.
+pub mod years {
+    include!(concat!(env!("OUT_DIR"), "/years.rs"));
+}
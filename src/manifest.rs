@@ -0,0 +1,154 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{Answer, Part, FIRST_YEAR};
+
+/// Known-correct answers, keyed by year, day and then input name, e.g.
+///
+/// ```toml
+/// [2024.13]
+/// input = { part1 = 12345, part2 = 67890 }
+/// example = { part1 = 480 }
+///
+/// [2024.14]
+/// example = { part1 = 12, params = { space = "7x11" } }
+/// ```
+///
+/// A day without a year, e.g. `[13]`, is from `FIRST_YEAR`.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    days: BTreeMap<(usize, usize), BTreeMap<String, Expected>>,
+}
+
+/// The expected answers for one input. A part without one isn't checked.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<ExpectedAnswer>,
+    pub part2: Option<ExpectedAnswer>,
+    /// Puzzle parameters to solve with, e.g. `params = { space = "7x11" }`
+    #[serde(default, deserialize_with = "params")]
+    pub params: BTreeMap<String, String>,
+}
+
+/// Numbers compare numerically; anything else compares by its display text.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ExpectedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Toml(toml::de::Error),
+    InvalidDay(String),
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        let raw: toml::Table = toml::from_str(text)?;
+        let mut days = BTreeMap::new();
+        for (key, value) in raw {
+            match key.parse() {
+                Ok(day @ 1..=25) => {
+                    days.insert((FIRST_YEAR, day), value.try_into()?);
+                }
+                Ok(year @ 1000..) => {
+                    let raw: BTreeMap<String, toml::Value> = value.try_into()?;
+                    for (day, inputs) in raw {
+                        match day.parse() {
+                            Ok(day @ 1..=25) => days.insert((year, day), inputs.try_into()?),
+                            _ => return Err(ManifestError::InvalidDay(format!("{year}.{day}"))),
+                        };
+                    }
+                }
+                _ => return Err(ManifestError::InvalidDay(key)),
+            }
+        }
+        Ok(Manifest { days })
+    }
+
+    pub fn contains(&self, year: usize, day: usize) -> bool {
+        self.days.contains_key(&(year, day))
+    }
+
+    /// Every listed input as (year, day, input name, expected answers),
+    /// ordered by year, day and then name.
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, &str, &Expected)> {
+        self.days.iter().flat_map(|(&(year, day), inputs)| {
+            inputs
+                .iter()
+                .map(move |(name, expected)| (year, day, name.as_str(), expected))
+        })
+    }
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&ExpectedAnswer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    /// The parts that have an expected answer.
+    pub fn parts(&self) -> Vec<Part> {
+        Part::BOTH
+            .into_iter()
+            .filter(|&part| self.get(part).is_some())
+            .collect()
+    }
+}
+
+impl ExpectedAnswer {
+    pub fn matches(&self, answer: &Answer) -> bool {
+        match self {
+            Self::Number(n) => answer == n,
+            Self::Text(text) => answer.to_string() == *text,
+        }
+    }
+}
+
+// Accepts numbers as well as strings, e.g. `bytes = 12`
+fn params<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    let params = BTreeMap::<String, ExpectedAnswer>::deserialize(deserializer)?;
+    Ok(params
+        .into_iter()
+        .map(|(name, value)| (name, value.to_string()))
+        .collect())
+}
+
+impl fmt::Display for ExpectedAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(err) => err.fmt(f),
+            Self::InvalidDay(day) => {
+                write!(
+                    f,
+                    "Invalid day [{day}]; expected a day or year.day, e.g. [2024.13]"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<toml::de::Error> for ManifestError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
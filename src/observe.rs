@@ -0,0 +1,48 @@
+use std::{fmt, sync::Arc};
+
+use ndarray::Array2;
+
+use crate::Part;
+
+/// Something a solver reports while it runs, for visualising or debugging it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A snapshot of the puzzle's grid after `step` steps.
+    Frame { step: u64, grid: Array2<char> },
+    /// How many of something the solver has gone through, e.g. ticks.
+    Counter { name: &'static str, value: u64 },
+    /// A state the solver picked out, e.g. the tick the robots form a tree.
+    Chosen { step: u64, description: String },
+}
+
+/// Where an event came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Origin {
+    pub day: usize,
+    pub part: Option<Part>,
+}
+
+/// Receives events from solvers. Solvers only build events when an
+/// observer is attached, so there's nothing to pay for without one.
+pub trait Observer: Send + Sync {
+    fn event(&self, origin: Origin, event: Event);
+}
+
+/// Sends each event to every observer in turn, e.g. to trace and export at once.
+impl Observer for Vec<Arc<dyn Observer>> {
+    fn event(&self, origin: Origin, event: Event) {
+        for observer in self {
+            observer.event(origin, event.clone());
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.part {
+            Some(Part::One) => write!(f, "Day {}, part 1", self.day),
+            Some(Part::Two) => write!(f, "Day {}, part 2", self.day),
+            None => write!(f, "Day {}", self.day),
+        }
+    }
+}
@@ -1,22 +1,44 @@
-use ndarray::{Array, Array2, Dimension, IntoDimension, NdIndex, ShapeError};
-use std::iter;
+use ndarray::{Array, Array2, Dimension, IntoDimension, NdIndex};
+use std::{convert::Infallible, fmt, iter};
+
+use crate::{error::Locate, SolveError};
 
 pub trait Array2Ext {
-    fn from_string<A, F>(input: &str, f: F) -> Result<Array2<A>, ShapeError>
+    fn from_string<A, F>(input: &str, f: F) -> Result<Array2<A>, SolveError>
     where
         F: Fn(char) -> A,
+    {
+        Self::try_from_string(input, |x| Ok::<_, Infallible>(f(x)))
+    }
+
+    fn try_from_string<A, E, F>(input: &str, f: F) -> Result<Array2<A>, SolveError>
+    where
+        E: fmt::Display,
+        F: Fn(char) -> Result<A, E>,
     {
         let mut flat_vec = Vec::new();
         let mut rows = 0;
-        let mut cols = 0;
-        for (r, row) in input.lines().enumerate() {
-            for (c, x) in row.chars().enumerate() {
-                flat_vec.push(f(x));
-                cols = c;
+        let mut cols = None;
+        // Pasted input often ends in a blank line
+        for row in input.trim_end_matches(['\r', '\n']).lines() {
+            let mut width = 0;
+            for (i, x) in row.char_indices() {
+                flat_vec.push(f(x).at(input, &row[i..i + x.len_utf8()])?);
+                width += 1;
+            }
+            match cols {
+                Some(cols) if cols != width => {
+                    let message = format!("Expected {cols} columns, found {width}");
+                    return Err(SolveError::input(input, row, message));
+                }
+                _ => cols = Some(width),
             }
-            rows = r;
+            rows += 1;
         }
-        Array2::from_shape_vec((rows + 1, cols + 1), flat_vec)
+        Ok(Array2::from_shape_vec(
+            (rows, cols.unwrap_or_default()),
+            flat_vec,
+        )?)
     }
 }
 
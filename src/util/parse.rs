@@ -0,0 +1,65 @@
+use std::iter;
+
+use regex::{Captures, Regex};
+
+use crate::SolveError;
+
+/// Like `Regex::captures_iter`, but rather than silently skipping text
+/// the pattern doesn't match, stops with an input error pointing at it.
+/// Whitespace between matches is allowed.
+pub fn captures_exact<'r, 'h>(
+    re: &'r Regex,
+    input: &'h str,
+) -> impl Iterator<Item = Result<Captures<'h>, SolveError>> + use<'r, 'h> {
+    let mut captures = re.captures_iter(input);
+    let mut pos = Some(0);
+    iter::from_fn(move || {
+        let start = pos?;
+        let next = captures.next();
+        let (end, next_pos) = match &next {
+            Some(c) => (c.get(0).unwrap().start(), Some(c.get(0).unwrap().end())),
+            None => (input.len(), None),
+        };
+        pos = next_pos;
+        let gap = input[start..end].trim();
+        if !gap.is_empty() {
+            pos = None;
+            let message = format!("Unexpected text {:?}", gap.lines().next().unwrap());
+            return Some(Err(SolveError::input(input, gap, message)));
+        }
+        next.map(Ok)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(input: &str) -> Vec<Result<&str, String>> {
+        let re = Regex::new(r"\d+").unwrap();
+        captures_exact(&re, input)
+            .map(|c| match c {
+                Ok(c) => Ok(c.get(0).unwrap().as_str()),
+                Err(error) => Err(error.to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn allows_whitespace_between_matches() {
+        assert_eq!(numbers(" 12 3\n\n45\n"), [Ok("12"), Ok("3"), Ok("45")]);
+        assert_eq!(numbers(""), []);
+    }
+
+    #[test]
+    fn stops_at_unmatched_text() {
+        let error = r#"Unexpected text "x y" (line 2, column 2)"#.to_string();
+        assert_eq!(numbers("12\n x y 3\n4"), [Ok("12"), Err(error)]);
+    }
+
+    #[test]
+    fn reports_unmatched_text_at_the_end() {
+        let error = r#"Unexpected text "é" (line 2, column 1)"#.to_string();
+        assert_eq!(numbers("12 3\né"), [Ok("12"), Ok("3"), Err(error)]);
+    }
+}
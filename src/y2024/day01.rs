@@ -0,0 +1,65 @@
+use crate::{error::Locate, Answer, Context, PartResult, SolveError, Solver};
+use std::{collections::HashMap, iter};
+
+pub type Columns = (Vec<u32>, Vec<u32>);
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input<'a> = Columns;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Columns, SolveError> {
+        parse(input)
+    }
+
+    fn part1(cols: &Columns, _cx: &Context) -> PartResult {
+        Ok(part1(cols.clone()))
+    }
+
+    fn part2(cols: &Columns, _cx: &Context) -> PartResult {
+        Ok(part2(cols))
+    }
+}
+
+fn parse(input: &str) -> Result<Columns, SolveError> {
+    let mut cols: Vec<Vec<u32>> = vec![];
+    for line in input.lines() {
+        for (i, num) in line.split_whitespace().enumerate() {
+            let mut col = cols.get_mut(i);
+            while col.is_none() {
+                cols.push(vec![]);
+                col = cols.get_mut(i);
+            }
+            col.unwrap().push(num.parse().at(input, num)?);
+        }
+    }
+    match (cols.pop(), cols.pop()) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        _ => Err(SolveError::input(
+            input,
+            input.lines().next().unwrap_or_default(),
+            "Input did not have 2 columns.",
+        )),
+    }
+}
+
+fn part1(mut cols: Columns) -> Answer {
+    cols.0.sort();
+    cols.1.sort();
+    iter::zip(&cols.0, &cols.1)
+        .map(|(a, b)| a.abs_diff(*b))
+        .sum::<u32>() // my first Turbofish
+        .into()
+}
+
+fn part2(cols: &Columns) -> Answer {
+    let mut counter: HashMap<u32, u32> = HashMap::new();
+    for &y in &cols.1 {
+        *counter.entry(y).or_insert(0) += 1;
+    }
+    cols.0
+        .iter()
+        .map(|&x| x * counter.get(&x).unwrap_or(&0))
+        .sum::<u32>()
+        .into()
+}
@@ -1,19 +1,43 @@
 use std::iter;
 
-use crate::SolveResult;
+use crate::{error::Locate, Answer, Context, PartResult, SolveError, Solver};
 
-pub fn solve(input: &str) -> SolveResult {
-    let reports = parse(input);
-    let report_diffs: Vec<Vec<i32>> = reports.iter().map(level_diffs).collect();
-    Ok((part1(&report_diffs), part2(&report_diffs)))
+pub type ReportDiffs = Vec<Vec<i32>>;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input<'a> = ReportDiffs;
+
+    fn parse(input: &str, _cx: &Context) -> Result<ReportDiffs, SolveError> {
+        let reports = parse(input)?;
+        Ok(reports.iter().map(level_diffs).collect())
+    }
+
+    fn part1(report_diffs: &ReportDiffs, _cx: &Context) -> PartResult {
+        Ok(part1(report_diffs))
+    }
+
+    fn part2(report_diffs: &ReportDiffs, _cx: &Context) -> PartResult {
+        Ok(part2(report_diffs))
+    }
 }
 
-fn parse(input: &str) -> Vec<Vec<u32>> {
+fn parse(input: &str) -> Result<Vec<Vec<u32>>, SolveError> {
+    // Pasted input often ends in a blank line
     input
+        .trim_end_matches(['\r', '\n'])
         .lines()
         .map(|line| {
+            if line.trim().is_empty() {
+                return Err(SolveError::input(
+                    input,
+                    line,
+                    "Expected a report of levels",
+                ));
+            }
             line.split_whitespace()
-                .filter_map(|x| x.parse().ok())
+                .map(|x| x.parse().at(input, x))
                 .collect()
         })
         .collect()
@@ -25,12 +49,8 @@ fn level_diffs(report: &Vec<u32>) -> Vec<i32> {
         .collect()
 }
 
-fn part1(report_diffs: &[Vec<i32>]) -> String {
-    report_diffs
-        .iter()
-        .flat_map(|x| analyze(x))
-        .count()
-        .to_string()
+fn part1(report_diffs: &[Vec<i32>]) -> Answer {
+    report_diffs.iter().flat_map(|x| analyze(x)).count().into()
 }
 
 fn analyze(diffs: &[i32]) -> Result<(), usize> {
@@ -44,12 +64,12 @@ fn analyze(diffs: &[i32]) -> Result<(), usize> {
     Ok(())
 }
 
-fn part2(report_diffs: &[Vec<i32>]) -> String {
+fn part2(report_diffs: &[Vec<i32>]) -> Answer {
     report_diffs
         .iter()
         .filter(|x| is_safe_with_dampener(x))
         .count()
-        .to_string()
+        .into()
 }
 
 fn is_safe_with_dampener(diffs: &[i32]) -> bool {
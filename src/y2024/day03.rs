@@ -1,22 +1,36 @@
 use regex::Regex;
 
-use crate::{SolveError, SolveResult};
+use crate::{error::Locate, Context, PartResult, SolveError, Solver};
 
-pub fn solve(input: &str) -> SolveResult {
-    Ok((part1(input)?, part2(input)?))
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input<'a> = &'a str;
+
+    fn parse<'a>(input: &'a str, _cx: &Context) -> Result<&'a str, SolveError> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str, _cx: &Context) -> PartResult {
+        part1(input)
+    }
+
+    fn part2(input: &&str, _cx: &Context) -> PartResult {
+        part2(input)
+    }
 }
 
-fn part1(input: &str) -> Result<String, SolveError> {
+fn part1(input: &str) -> PartResult {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)")?;
     let mut sum: u32 = 0;
     for (_, [x, y]) in re.captures_iter(input).map(|c| c.extract()) {
-        let (x, y): (u32, u32) = (x.parse()?, y.parse()?);
+        let (x, y): (u32, u32) = (x.parse().at(input, x)?, y.parse().at(input, y)?);
         sum += x * y;
     }
-    Ok(sum.to_string())
+    Ok(sum.into())
 }
 
-fn part2(input: &str) -> Result<String, SolveError> {
+fn part2(input: &str) -> PartResult {
     let cmd_re = Regex::new(r"mul|don't|do")?;
     let mul_re = Regex::new(r"^\((\d{1,3}),(\d{1,3})\)")?;
     let other_re = Regex::new(r"^\(\)")?;
@@ -35,7 +49,8 @@ fn part2(input: &str) -> Result<String, SolveError> {
                     "do" => enabled = true,
                     "don't" => enabled = false,
                     "mul" if enabled => {
-                        let (x, y): (u32, u32) = (n[1].parse()?, n[2].parse()?);
+                        let (x, y) = (&n[1], &n[2]);
+                        let (x, y): (u32, u32) = (x.parse().at(input, x)?, y.parse().at(input, y)?);
                         sum += x * y;
                     }
                     _ => {} // nothing to do
@@ -47,5 +62,5 @@ fn part2(input: &str) -> Result<String, SolveError> {
             }
         }
     }
-    Ok(sum.to_string())
+    Ok(sum.into())
 }
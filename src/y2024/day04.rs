@@ -1,6 +1,6 @@
 use std::iter;
 
-use crate::{Error, SolveError, SolveResult};
+use crate::{util::grid::Array2Ext, Answer, Context, PartResult, SolveError, Solver};
 use ndarray::{indices_of, prelude::*};
 
 enum Direction {
@@ -27,24 +27,32 @@ impl Direction {
     ];
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let grid = parse(input)?;
-    Ok((part1(&grid), part2(&grid)))
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input<'a> = Array2<char>;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Array2<char>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Array2<char>, _cx: &Context) -> PartResult {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Array2<char>, _cx: &Context) -> PartResult {
+        Ok(part2(grid))
+    }
 }
 
 fn parse(input: &str) -> Result<Array2<char>, SolveError> {
-    let rows = input.lines().count();
-    let cols = input
-        .lines()
-        .next()
-        .ok_or(Error::InputError("Empty input"))?
-        .len();
-    let input = input.lines().flat_map(str::chars).collect();
-    let arr = Array2::from_shape_vec((rows, cols), input)?;
-    Ok(arr)
+    if input.trim().is_empty() {
+        return Err(SolveError::missing(input, "Empty input"));
+    }
+    Array2::<char>::from_string(input, |x| x)
 }
 
-fn part1(grid: &Array2<char>) -> String {
+fn part1(grid: &Array2<char>) -> Answer {
     let token = "XMAS";
     let mut count: u32 = 0;
     for origin in indices_of(grid) {
@@ -54,10 +62,10 @@ fn part1(grid: &Array2<char>) -> String {
             }
         }
     }
-    count.to_string()
+    count.into()
 }
 
-fn part2(grid: &Array2<char>) -> String {
+fn part2(grid: &Array2<char>) -> Answer {
     let token = "MAS";
     let mut count: u32 = 0;
     for origin in indices_of(grid) {
@@ -75,7 +83,7 @@ fn part2(grid: &Array2<char>) -> String {
             count += 1;
         }
     }
-    count.to_string()
+    count.into()
 }
 
 fn starts_with_from(
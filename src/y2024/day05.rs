@@ -1,21 +1,36 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{Error, SolveError, SolveResult};
+use crate::{error::Locate, Answer, Context, PartResult, SolveError, Solver};
 
-struct Rules {
-    depends_on: HashMap<u32, HashSet<u32>>,
-    fulfills: HashMap<u32, Vec<u32>>,
+pub struct Rules {
+    pub depends_on: HashMap<u32, HashSet<u32>>,
+    pub fulfills: HashMap<u32, Vec<u32>>,
 }
 
-type Update = Vec<u32>;
+pub type Update = Vec<u32>;
 
-pub fn solve(input: &str) -> SolveResult {
-    let (rules, updates) = parse(input)?;
-    let (valid, invalid): (Vec<Update>, Vec<Update>) =
-        updates.into_iter().partition(|u| is_valid(&rules, u));
+pub struct Day05;
 
-    let part1 = score(&valid);
-    Ok((part1, part2(rules, invalid)))
+impl Solver for Day05 {
+    type Input<'a> = (Rules, Vec<Update>);
+
+    fn parse(input: &str, _cx: &Context) -> Result<(Rules, Vec<Update>), SolveError> {
+        parse(input)
+    }
+
+    fn part1((rules, updates): &(Rules, Vec<Update>), _cx: &Context) -> PartResult {
+        let valid: Vec<Update> = updates
+            .iter()
+            .filter(|u| is_valid(rules, u))
+            .cloned()
+            .collect();
+        Ok(score(&valid))
+    }
+
+    fn part2((rules, updates): &(Rules, Vec<Update>), _cx: &Context) -> PartResult {
+        let invalid: Vec<&Update> = updates.iter().filter(|u| !is_valid(rules, u)).collect();
+        Ok(part2(rules, invalid))
+    }
 }
 
 fn parse(input: &str) -> Result<(Rules, Vec<Update>), SolveError> {
@@ -29,25 +44,21 @@ fn parse(input: &str) -> Result<(Rules, Vec<Update>), SolveError> {
         // u|v == v depends on u
         let (u, v) = line
             .split_once('|')
-            .ok_or(Error::InputError("No delimiter in graph input"))?;
-        let (u, v) = (u.parse()?, v.parse()?);
+            .ok_or_else(|| SolveError::input(input, line, "No delimiter in graph input"))?;
+        let (u, v) = (u.parse().at(input, u)?, v.parse().at(input, v)?);
         rules.fulfills.entry(u).or_default().push(v);
         rules.depends_on.entry(v).or_default().insert(u);
     }
 
-    let updates: Result<Vec<Update>, _> = lines
-        .map(|line| line.split(',').map(|p| p.parse()).collect())
-        .collect();
+    let updates = lines
+        .map(|line| line.split(',').map(|p| p.parse().at(input, p)).collect())
+        .collect::<Result<_, _>>()?;
 
-    Ok((rules, updates?))
+    Ok((rules, updates))
 }
 
-fn score(updates: &[Update]) -> String {
-    updates
-        .iter()
-        .map(|u| u[u.len() / 2])
-        .sum::<u32>()
-        .to_string()
+fn score(updates: &[Update]) -> Answer {
+    updates.iter().map(|u| u[u.len() / 2]).sum::<u32>().into()
 }
 
 fn is_valid(rules: &Rules, update: &Update) -> bool {
@@ -65,11 +76,11 @@ fn is_valid(rules: &Rules, update: &Update) -> bool {
     true
 }
 
-fn part2(rules: Rules, updates: Vec<Update>) -> String {
+fn part2(rules: &Rules, updates: Vec<&Update>) -> Answer {
     let mut valid: Vec<Update> = Vec::new();
     for update in updates {
-        let update_set: HashSet<u32> = HashSet::from_iter(update);
-        valid.push(reorder(&rules, update_set));
+        let update_set: HashSet<u32> = update.iter().copied().collect();
+        valid.push(reorder(rules, update_set));
     }
     score(&valid)
 }
@@ -81,7 +92,7 @@ fn reorder(rules: &Rules, update: HashSet<u32>) -> Update {
             rules
                 .depends_on
                 .get(p)
-                .map_or(true, |d| d.is_disjoint(&update))
+                .is_none_or(|d| d.is_disjoint(&update))
         })
         .collect();
 
@@ -3,13 +3,23 @@ use std::{
     ops::Bound::{Excluded, Unbounded},
 };
 
-use crate::SolveResult;
+use ndarray::Array2;
 
-struct Grid {
-    rows: HashMap<usize, BTreeSet<usize>>,
-    cols: HashMap<usize, BTreeSet<usize>>,
-    start: (usize, usize),
-    size: (usize, usize),
+use crate::{observe::Event, Answer, Context, LogLevel, PartResult, SolveError, Solver};
+
+#[derive(Clone)]
+pub struct Grid {
+    pub rows: HashMap<usize, BTreeSet<usize>>,
+    pub cols: HashMap<usize, BTreeSet<usize>>,
+    pub start: (usize, usize),
+    pub size: (usize, usize),
+}
+
+/// The lab map together with the corners of the guard's unobstructed route,
+/// which are filled in by `prepare`.
+pub struct Patrol {
+    pub grid: Grid,
+    pub vertices: Vec<(usize, usize)>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -31,24 +41,49 @@ impl Direction {
     }
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let mut grid = parse(input);
-    let vertices = calc_vertices(&grid);
-    Ok((part1(&grid, &vertices), part2(&mut grid, &vertices)))
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input<'a> = Patrol;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Patrol, SolveError> {
+        Ok(Patrol {
+            grid: parse(input)?,
+            vertices: Vec::new(),
+        })
+    }
+
+    fn prepare(patrol: &mut Patrol, _cx: &Context) -> Result<(), SolveError> {
+        patrol.vertices = calc_vertices(&patrol.grid);
+        Ok(())
+    }
+
+    fn part1(patrol: &Patrol, cx: &Context) -> PartResult {
+        Ok(part1(&patrol.grid, &patrol.vertices, cx))
+    }
+
+    fn part2(patrol: &Patrol, cx: &Context) -> PartResult {
+        part2(&mut patrol.grid.clone(), &patrol.vertices, cx)
+    }
 }
 
-fn parse(input: &str) -> Grid {
+fn parse(input: &str) -> Result<Grid, SolveError> {
     let mut grid = Grid {
         rows: HashMap::new(),
         cols: HashMap::new(),
         start: (0, 0),
         size: (0, 0),
     };
+    let mut guard = None;
     for (r, row) in input.lines().enumerate() {
-        for (c, x) in row.chars().enumerate() {
+        for ((c, x), (i, _)) in row.chars().enumerate().zip(row.char_indices()) {
             grid.size = (r, c);
             match x {
-                '^' => grid.start = (r, c),
+                '^' if guard.is_some() => {
+                    let span = &row[i..i + 1];
+                    return Err(SolveError::input(input, span, "Expected only one guard"));
+                }
+                '^' => guard = Some((r, c)),
                 '#' => {
                     grid.rows.entry(r).or_default().insert(c);
                     grid.cols.entry(c).or_default().insert(r);
@@ -57,8 +92,10 @@ fn parse(input: &str) -> Grid {
             };
         }
     }
+    let missing = || SolveError::missing(input, "Expected a guard (^) on the map");
+    grid.start = guard.ok_or_else(missing)?;
     grid.size = (grid.size.0 + 1, grid.size.1 + 1);
-    grid
+    Ok(grid)
 }
 
 fn calc_vertices(grid: &Grid) -> Vec<(usize, usize)>
@@ -132,10 +169,14 @@ fn final_dest(grid: &Grid, pos: &(usize, usize), dir: &Direction) -> (usize, usi
     }
 }
 
-fn part1(grid: &Grid, vertices: &[(usize, usize)]) -> String {
+fn part1(grid: &Grid, vertices: &[(usize, usize)], cx: &Context) -> Answer {
     let mut buffer = vec![vec![false; grid.size.1]; grid.size.0];
     let mut dir = Direction::North;
-    for (p1, p2) in vertices.iter().zip(vertices[1..].iter()) {
+    cx.observe(|| Event::Frame {
+        step: 0,
+        grid: frame(grid, &buffer, grid.start, dir),
+    });
+    for (step, (p1, p2)) in (1..).zip(vertices.iter().zip(vertices[1..].iter())) {
         let r = match dir {
             Direction::North => p2.0..=p1.0,
             Direction::East => p1.1..=p2.1,
@@ -148,13 +189,43 @@ fn part1(grid: &Grid, vertices: &[(usize, usize)]) -> String {
                 Direction::East | Direction::West => buffer[p1.0][x] = true,
             }
         }
+        // One frame per leg of the route, with the guard where it turns
+        cx.observe(|| Event::Frame {
+            step,
+            grid: frame(grid, &buffer, *p2, dir),
+        });
         dir = dir.turn();
     }
-    buffer.iter().flatten().filter(|&x| *x).count().to_string()
+    buffer.iter().flatten().filter(|&x| *x).count().into()
+}
+
+fn frame(
+    grid: &Grid,
+    visited: &[Vec<bool>],
+    guard: (usize, usize),
+    dir: Direction,
+) -> Array2<char> {
+    let mut frame = Array2::from_shape_fn(grid.size, |(r, c)| match visited[r][c] {
+        true => 'X',
+        false => '.',
+    });
+    for (&r, cols) in &grid.rows {
+        for &c in cols {
+            frame[(r, c)] = '#';
+        }
+    }
+    frame[guard] = match dir {
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<',
+    };
+    frame
 }
 
-fn part2(grid: &mut Grid, vertices: &[(usize, usize)]) -> String {
+fn part2(grid: &mut Grid, vertices: &[(usize, usize)], cx: &Context) -> PartResult {
     let mut blocks = HashSet::new();
+    let mut checked = 0;
     let mut dir = Direction::North;
     for (p1, p2) in vertices.iter().zip(vertices[1..].iter()) {
         let r = match dir {
@@ -173,9 +244,12 @@ fn part2(grid: &mut Grid, vertices: &[(usize, usize)]) -> String {
             if block == grid.start || blocks.contains(&block) {
                 continue;
             }
+            cx.check()?;
             grid.rows.entry(block.0).or_default().insert(block.1);
             grid.cols.entry(block.1).or_default().insert(block.0);
+            checked += 1;
             if check_loop(grid) {
+                cx.log(LogLevel::Debug, || format!("obstruction at {block:?} makes a loop"));
                 blocks.insert(block);
             }
             grid.rows.get_mut(&block.0).map(|v| v.remove(&block.1));
@@ -183,7 +257,8 @@ fn part2(grid: &mut Grid, vertices: &[(usize, usize)]) -> String {
         }
         dir = dir.turn();
     }
-    blocks.len().to_string()
+    cx.log(LogLevel::Info, || format!("checked {checked} obstructions"));
+    Ok(blocks.len().into())
 }
 
 fn check_loop(grid: &Grid) -> bool {
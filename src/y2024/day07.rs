@@ -0,0 +1,112 @@
+use regex::Regex;
+
+use crate::{error::Locate, util::parse::captures_exact, Context, PartResult, SolveError, Solver};
+
+pub struct CalibrationEquation {
+    pub test_value: u64,
+    pub terms: Vec<u64>,
+}
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Input<'a> = Vec<CalibrationEquation>;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Vec<CalibrationEquation>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(equations: &Vec<CalibrationEquation>, cx: &Context) -> PartResult {
+        part1(equations, cx)
+    }
+
+    fn part2(equations: &Vec<CalibrationEquation>, cx: &Context) -> PartResult {
+        part2(equations, cx)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<CalibrationEquation>, SolveError> {
+    let re = Regex::new(r"(\d+): (\d+(?: \d+)*)")?;
+    captures_exact(&re, input)
+        .map(|c| {
+            let (_, [v, ts]) = c?.extract();
+            Ok(CalibrationEquation {
+                test_value: v.parse().at(input, v)?,
+                terms: parse_terms(input, ts)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_terms(input: &str, terms: &str) -> Result<Vec<u64>, SolveError> {
+    terms
+        .split_whitespace()
+        .map(|t| t.parse().at(input, t))
+        .collect()
+}
+
+fn part1(equations: &[CalibrationEquation], cx: &Context) -> PartResult {
+    let get_candidates = |x, y| [x + y, x * y];
+    Ok(total_calibration(equations, get_candidates, cx)?.into())
+}
+
+fn part2(equations: &[CalibrationEquation], cx: &Context) -> PartResult {
+    let get_candidates = |x, y| [x + y, x * y, concat_digits(x, y)];
+    Ok(total_calibration(equations, get_candidates, cx)?.into())
+}
+
+fn concat_digits(x: u64, y: u64) -> u64 {
+    let n = y.checked_ilog10().unwrap_or_default() + 1;
+    x * 10u64.pow(n) + y
+}
+
+fn total_calibration<F, I>(
+    equations: &[CalibrationEquation],
+    get_candidates: F,
+    cx: &Context,
+) -> Result<u64, SolveError>
+where
+    F: Fn(u64, u64) -> I,
+    I: IntoIterator<Item = u64>,
+{
+    let mut total = 0;
+    for equation in equations {
+        if validate(equation, &get_candidates, cx)? {
+            total += equation.test_value;
+        }
+    }
+    Ok(total)
+}
+
+fn validate<F, I>(
+    equation: &CalibrationEquation,
+    get_candidates: F,
+    cx: &Context,
+) -> Result<bool, SolveError>
+where
+    F: Fn(u64, u64) -> I,
+    I: IntoIterator<Item = u64>,
+{
+    let mut stack = vec![(1, equation.terms[0])];
+    // The ways to combine terms grow exponentially with their number
+    let mut steps: u64 = 0;
+    while let Some((i, t)) = stack.pop() {
+        steps += 1;
+        if steps.is_multiple_of(4096) {
+            cx.check()?;
+        }
+        if i >= equation.terms.len() {
+            if t == equation.test_value {
+                return Ok(true);
+            }
+            continue;
+        }
+        let candidates = get_candidates(t, equation.terms[i]);
+        for candidate in candidates {
+            if candidate <= equation.test_value {
+                stack.push((i + 1, candidate));
+            }
+        }
+    }
+    Ok(false)
+}
@@ -3,14 +3,14 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
-use crate::SolveResult;
+use crate::{Context, PartResult, SolveError, Solver};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Vec2(i64, i64);
+pub struct Vec2(pub i64, pub i64);
 
-struct Antennas {
-    locations: HashMap<char, HashSet<Vec2>>,
-    size: Vec2,
+pub struct Antennas {
+    pub locations: HashMap<char, HashSet<Vec2>>,
+    pub size: Vec2,
 }
 
 impl Add for Vec2 {
@@ -43,9 +43,22 @@ impl SubAssign for Vec2 {
     }
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let antennas = parse(input);
-    Ok((part1(&antennas), part2(&antennas)))
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Input<'a> = Antennas;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Antennas, SolveError> {
+        Ok(parse(input))
+    }
+
+    fn part1(antennas: &Antennas, cx: &Context) -> PartResult {
+        part1(antennas, cx)
+    }
+
+    fn part2(antennas: &Antennas, cx: &Context) -> PartResult {
+        part2(antennas, cx)
+    }
 }
 
 fn parse(input: &str) -> Antennas {
@@ -69,10 +82,11 @@ fn parse(input: &str) -> Antennas {
     antennas
 }
 
-fn part1(antennas: &Antennas) -> String {
+fn part1(antennas: &Antennas, cx: &Context) -> PartResult {
     let get_antinodes = |nodes: &[Vec2]| {
         let mut result = HashSet::new();
         for i in 0..nodes.len() {
+            cx.check()?;
             for j in i + 1..nodes.len() {
                 let i_to_j = nodes[j] - nodes[i];
                 let an1 = nodes[i] - i_to_j;
@@ -85,15 +99,16 @@ fn part1(antennas: &Antennas) -> String {
                 }
             }
         }
-        result
+        Ok(result)
     };
-    count_antinodes(antennas, get_antinodes).to_string()
+    Ok(count_antinodes(antennas, get_antinodes)?.into())
 }
 
-fn part2(antennas: &Antennas) -> String {
+fn part2(antennas: &Antennas, cx: &Context) -> PartResult {
     let get_antinodes = |nodes: &[Vec2]| {
         let mut result = HashSet::new();
         for i in 0..nodes.len() {
+            cx.check()?;
             for j in i + 1..nodes.len() {
                 let i_to_j = nodes[j] - nodes[i];
                 let mut an = nodes[i];
@@ -108,24 +123,22 @@ fn part2(antennas: &Antennas) -> String {
                 }
             }
         }
-        result
+        Ok(result)
     };
-    count_antinodes(antennas, get_antinodes).to_string()
+    Ok(count_antinodes(antennas, get_antinodes)?.into())
 }
 
-fn count_antinodes<F>(antennas: &Antennas, get_antinodes: F) -> usize
+fn count_antinodes<F>(antennas: &Antennas, get_antinodes: F) -> Result<usize, SolveError>
 where
-    F: Fn(&[Vec2]) -> HashSet<Vec2>,
+    F: Fn(&[Vec2]) -> Result<HashSet<Vec2>, SolveError>,
 {
-    antennas
-        .locations
-        .values()
-        .map(|nodes| get_antinodes(&nodes.iter().copied().collect::<Vec<Vec2>>()))
-        .reduce(|mut acc, x| {
-            acc.extend(x);
-            acc
-        })
-        .map_or(0, |v| v.len())
+    let mut antinodes = HashSet::new();
+    for nodes in antennas.locations.values() {
+        antinodes.extend(get_antinodes(
+            &nodes.iter().copied().collect::<Vec<Vec2>>(),
+        )?);
+    }
+    Ok(antinodes.len())
 }
 
 fn is_valid(antennas: &Antennas, antinode: &Vec2) -> bool {
@@ -1,9 +1,9 @@
 use std::{fmt, iter::repeat_n};
 
-use crate::{Error, SolveError, SolveResult};
+use crate::{Answer, Context, PartResult, SolveError, Solver};
 
 #[derive(Clone, Copy, Debug)]
-enum BlockBlock {
+pub enum BlockBlock {
     File { id: u32, size: usize },
     Free { size: usize },
 }
@@ -23,17 +23,33 @@ impl fmt::Debug for Block {
     }
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let blocks = parse(input)?;
-    Ok((part1(blocks.iter()), part2(blocks)))
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input<'a> = Vec<BlockBlock>;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Vec<BlockBlock>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(blocks: &Vec<BlockBlock>, _cx: &Context) -> PartResult {
+        Ok(part1(blocks.iter()))
+    }
+
+    fn part2(blocks: &Vec<BlockBlock>, cx: &Context) -> PartResult {
+        part2(blocks.clone(), cx)
+    }
 }
 
 fn parse(input: &str) -> Result<Vec<BlockBlock>, SolveError> {
     let mut blocks = Vec::new();
     let mut is_file_block = true;
     let mut file_id = 0;
-    for c in input.trim().chars() {
-        let length = c.to_digit(10).ok_or(Error::InputError("Invalid digit"))? as usize;
+    let disk_map = input.trim();
+    for (i, c) in disk_map.char_indices() {
+        let length = c.to_digit(10).ok_or_else(|| {
+            SolveError::input(input, &disk_map[i..i + c.len_utf8()], "Invalid digit")
+        })? as usize;
         if is_file_block {
             blocks.push(BlockBlock::File {
                 id: file_id,
@@ -48,7 +64,7 @@ fn parse(input: &str) -> Result<Vec<BlockBlock>, SolveError> {
     Ok(blocks)
 }
 
-fn part1<'a, I>(block_blocks: I) -> String
+fn part1<'a, I>(block_blocks: I) -> Answer
 where
     I: Iterator<Item = &'a BlockBlock>,
 {
@@ -68,14 +84,15 @@ where
         }
         i += 1;
     }
-    checksum(blocks.iter()).to_string()
+    checksum(blocks.iter()).into()
 }
 
-fn part2(mut block_blocks: Vec<BlockBlock>) -> String {
+fn part2(mut block_blocks: Vec<BlockBlock>, cx: &Context) -> PartResult {
     // This is a little inefficient with a Vec<>, but std:collections::LinkedList
     // didn't seem provide a stable API for inserting & removing from within the list,
     // which defeats the point.
     for source_index in (0..block_blocks.len()).rev() {
+        cx.check()?;
         if let file @ BlockBlock::File {
             size: file_size, ..
         } = block_blocks[source_index]
@@ -102,7 +119,7 @@ fn part2(mut block_blocks: Vec<BlockBlock>) -> String {
     }
 
     let blocks = flatten_block_blocks(block_blocks.iter());
-    checksum(blocks.iter()).to_string()
+    Ok(checksum(blocks.iter()).into())
 }
 
 fn flatten_block_blocks<'a, I>(block_blocks: I) -> Vec<Block>
@@ -1,37 +1,42 @@
 use std::collections::HashSet;
 
-use crate::{util::grid::ArrayExt, Error, SolveError, SolveResult};
+use crate::{
+    util::grid::{Array2Ext, ArrayExt},
+    Answer, Context, PartResult, SolveError, Solver,
+};
 use ndarray::prelude::*;
 
-pub fn solve(input: &str) -> SolveResult {
-    let top_map = parse(input)?;
-    Ok((part1(&top_map), part2(&top_map)))
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input<'a> = Array2<u32>;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Array2<u32>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(top_map: &Array2<u32>, _cx: &Context) -> PartResult {
+        Ok(part1(top_map))
+    }
+
+    fn part2(top_map: &Array2<u32>, _cx: &Context) -> PartResult {
+        Ok(part2(top_map))
+    }
 }
 
 fn parse(input: &str) -> Result<Array2<u32>, SolveError> {
-    let rows = input.lines().count();
-    let cols = input
-        .lines()
-        .next()
-        .ok_or(Error::InputError("Empty input"))?
-        .len();
-    let top_map = input
-        .lines()
-        .flat_map(str::chars)
-        .map(|h| {
-            h.to_digit(10)
-                .or(Some(10))
-                .ok_or(Error::InputError("Invalid digit"))
-        })
-        .collect::<Result<_, _>>()?;
-    Ok(Array2::from_shape_vec((rows, cols), top_map)?)
+    if input.trim().is_empty() {
+        return Err(SolveError::missing(input, "Empty input"));
+    }
+    // Impassable tiles (e.g. '.') are one level above any summit
+    Array2::<u32>::from_string(input, |h| h.to_digit(10).unwrap_or(10))
 }
 
 // The shared parts of part1 & 2 should really be extracted into a shared function,
 // but I spent so long on that stupid neighbors() implementation above
 // I don't have it in me right now.
 
-fn part1(top_map: &Array2<u32>) -> String {
+fn part1(top_map: &Array2<u32>) -> Answer {
     let mut level = 9;
     let level9 = top_map.indexed_iter().filter(|(_, &h)| h == level);
 
@@ -43,7 +48,7 @@ fn part1(top_map: &Array2<u32>) -> String {
     }
 
     let mut next = HashSet::new();
-    while level > 0 && current.len() > 0 {
+    while level > 0 && !current.is_empty() {
         level -= 1;
         for ix in current.drain() {
             let lower = top_map.neighbors(ix).filter(|&nix| top_map[nix] == level);
@@ -62,10 +67,10 @@ fn part1(top_map: &Array2<u32>) -> String {
         .filter(|(_, &h)| h == 0)
         .map(|(ix, _)| flood_map[ix].len())
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
-fn part2(top_map: &Array2<u32>) -> String {
+fn part2(top_map: &Array2<u32>) -> Answer {
     let mut level = 9;
     let level9 = top_map.indexed_iter().filter(|(_, &h)| h == level);
 
@@ -77,7 +82,7 @@ fn part2(top_map: &Array2<u32>) -> String {
     }
 
     let mut next = HashSet::new();
-    while level > 0 && current.len() > 0 {
+    while level > 0 && !current.is_empty() {
         level -= 1;
         for ix in current.drain() {
             let lower = top_map.neighbors(ix).filter(|&nix| top_map[nix] == level);
@@ -94,5 +99,5 @@ fn part2(top_map: &Array2<u32>) -> String {
         .filter(|(_, &h)| h == 0)
         .map(|(ix, _)| flood_map[ix])
         .sum::<usize>()
-        .to_string()
+        .into()
 }
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use crate::{error::Locate, Answer, Context, PartResult, SolveError, Solver};
+
+pub type Stones = HashMap<u64, usize>;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input<'a> = Stones;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Stones, SolveError> {
+        parse(input)
+    }
+
+    fn part1(stones: &Stones, _cx: &Context) -> PartResult {
+        Ok(part1(&mut stones.clone()))
+    }
+
+    fn part2(stones: &Stones, _cx: &Context) -> PartResult {
+        Ok(part2(&mut stones.clone()))
+    }
+}
+
+fn parse(input: &str) -> Result<Stones, SolveError> {
+    let mut stones = HashMap::new();
+    for stone in input.split_whitespace() {
+        *stones.entry(stone.parse().at(input, stone)?).or_default() += 1;
+    }
+    Ok(stones)
+}
+
+fn part1(stones: &mut Stones) -> Answer {
+    blink_n(stones, 25);
+    stones.values().sum::<usize>().into()
+}
+
+fn part2(stones: &mut Stones) -> Answer {
+    blink_n(stones, 75);
+    stones.values().sum::<usize>().into()
+}
+
+fn blink_n(stones: &mut Stones, n: usize) {
+    let mut current = stones;
+    let mut other = Stones::new();
+    let mut next = &mut other;
+    for _ in 0..n {
+        for (stone, count) in current.drain() {
+            match stone {
+                0 => *next.entry(1).or_default() += count,
+                x if (x.ilog10() + 1).is_multiple_of(2) => {
+                    let (left, right) = cleave(x);
+                    *next.entry(left).or_default() += count;
+                    *next.entry(right).or_default() += count;
+                }
+                x => *next.entry(x * 2024).or_default() += count,
+            }
+        }
+        (current, next) = (next, current);
+    }
+    if !n.is_multiple_of(2) {
+        next.extend(current.drain());
+    }
+}
+
+fn cleave(stone: u64) -> (u64, u64) {
+    let magnitude = 10u64.pow(stone.ilog10().div_ceil(2));
+    (stone / magnitude, stone % magnitude)
+}
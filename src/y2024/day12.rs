@@ -1,40 +1,69 @@
 use std::collections::HashMap;
 
-use crate::{util::grid::Array2Ext, SolveResult};
+use crate::{util::grid::Array2Ext, Answer, Context, PartResult, SolveError, Solver};
 use ndarray::Array2;
 use ndarray_ndimage::{pad, PadMode};
 
-type Farm = Array2<char>;
+pub type Farm = Array2<char>;
 
 #[derive(Debug)]
-struct Region {
-    label: usize,
-    area: usize,
-    perimeter: usize,
-    sides: usize,
+pub struct Region {
+    pub label: usize,
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let farm = Farm::from_string(input, |x| x)?;
-    let (mut regions, plot_labels) = label_regions(&farm);
-    count_sides(&mut regions, &plot_labels);
-    Ok((part1(&regions), part2(&regions)))
+pub type Regions = HashMap<usize, Region>;
+
+/// The farm map and its regions, which are labelled by `prepare`.
+pub struct Garden {
+    pub farm: Farm,
+    pub regions: Regions,
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input<'a> = Garden;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Garden, SolveError> {
+        Ok(Garden {
+            farm: Farm::from_string(input, |x| x)?,
+            regions: Regions::new(),
+        })
+    }
+
+    fn prepare(garden: &mut Garden, _cx: &Context) -> Result<(), SolveError> {
+        let (mut regions, plot_labels) = label_regions(&garden.farm);
+        count_sides(&mut regions, &plot_labels);
+        garden.regions = regions;
+        Ok(())
+    }
+
+    fn part1(garden: &Garden, _cx: &Context) -> PartResult {
+        Ok(part1(&garden.regions))
+    }
+
+    fn part2(garden: &Garden, _cx: &Context) -> PartResult {
+        Ok(part2(&garden.regions))
+    }
 }
 
-fn part1(regions: &HashMap<usize, Region>) -> String {
+fn part1(regions: &HashMap<usize, Region>) -> Answer {
     regions
         .values()
         .map(|r| r.area * r.perimeter)
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
-fn part2(regions: &HashMap<usize, Region>) -> String {
+fn part2(regions: &HashMap<usize, Region>) -> Answer {
     regions
         .values()
         .map(|r| r.area * r.sides)
         .sum::<usize>()
-        .to_string()
+        .into()
 }
 
 fn label_regions(farm: &Farm) -> (HashMap<usize, Region>, Array2<usize>) {
@@ -107,7 +136,7 @@ fn label_regions(farm: &Farm) -> (HashMap<usize, Region>, Array2<usize>) {
     (combine_regions(regions), plot_labels)
 }
 
-fn find_root(regions: &mut Vec<Region>, label: usize) -> usize {
+fn find_root(regions: &mut [Region], label: usize) -> usize {
     let mut root = label;
     while regions[root].label != root {
         root = regions[root].label;
@@ -122,7 +151,7 @@ fn find_root(regions: &mut Vec<Region>, label: usize) -> usize {
     root
 }
 
-fn union_roots(regions: &mut Vec<Region>, mut x: usize, mut y: usize) {
+fn union_roots(regions: &mut [Region], mut x: usize, mut y: usize) {
     x = find_root(regions, x);
     y = find_root(regions, y);
     match x.cmp(&y) {
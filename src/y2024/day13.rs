@@ -2,12 +2,14 @@ use ndarray::Array2;
 use num::Rational64;
 use regex::Regex;
 
-use crate::{SolveError, SolveResult};
+use crate::{
+    error::Locate, util::parse::captures_exact, Answer, Context, PartResult, SolveError, Solver,
+};
 
 #[derive(Debug, Clone)]
-struct ClawMachine {
-    buttons: Array2<i64>,
-    prize: Array2<i64>,
+pub struct ClawMachine {
+    pub buttons: Array2<i64>,
+    pub prize: Array2<i64>,
 }
 
 impl ClawMachine {
@@ -30,9 +32,22 @@ impl ClawMachine {
     }
 }
 
-pub fn solve(input: &str) -> SolveResult {
-    let machines = parse(input)?;
-    Ok((part1(&machines), part2(&machines)))
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input<'a> = Vec<ClawMachine>;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Vec<ClawMachine>, SolveError> {
+        parse(input)
+    }
+
+    fn part1(machines: &Vec<ClawMachine>, _cx: &Context) -> PartResult {
+        Ok(part1(machines))
+    }
+
+    fn part2(machines: &Vec<ClawMachine>, _cx: &Context) -> PartResult {
+        Ok(part2(machines))
+    }
 }
 
 fn parse(input: &str) -> Result<Vec<ClawMachine>, SolveError> {
@@ -41,33 +56,33 @@ fn parse(input: &str) -> Result<Vec<ClawMachine>, SolveError> {
     pattern.push_str(r"Button B: X\+(\d+), Y\+(\d+)\r?\n");
     pattern.push_str(r"Prize: X=(\d+), Y=(\d+)(?:\r?\n)*");
     let re = Regex::new(pattern.as_str())?;
-    let machines = re
-        .captures_iter(input)
-        .map(|c| c.extract())
-        .map(|(_, [ax, ay, bx, by, px, py])| {
-            Ok::<_, SolveError>(ClawMachine {
+    let machines = captures_exact(&re, input)
+        .map(|c| {
+            let (_, [ax, ay, bx, by, px, py]) = c?.extract();
+            let parse = |x: &str| x.parse().at(input, x);
+            Ok(ClawMachine {
                 buttons: Array2::from_shape_vec(
                     (2, 2),
-                    vec![ax.parse()?, bx.parse()?, ay.parse()?, by.parse()?],
+                    vec![parse(ax)?, parse(bx)?, parse(ay)?, parse(by)?],
                 )?,
-                prize: Array2::from_shape_vec((2, 1), vec![px.parse()?, py.parse()?])?,
+                prize: Array2::from_shape_vec((2, 1), vec![parse(px)?, parse(py)?])?,
             })
         })
-        .collect::<Result<_, _>>()?;
+        .collect::<Result<_, SolveError>>()?;
     Ok(machines)
 }
 
-fn part1(machines: &[ClawMachine]) -> String {
+fn part1(machines: &[ClawMachine]) -> Answer {
     machines
         .iter()
         .filter_map(min_tokens)
         .filter(|(a, b)| *a <= 100 && *b <= 100)
         .map(|(a, b)| 3 * a + b)
         .sum::<i64>()
-        .to_string()
+        .into()
 }
 
-fn part2(machines: &[ClawMachine]) -> String {
+fn part2(machines: &[ClawMachine]) -> Answer {
     let machines: Vec<_> = machines
         .iter()
         .cloned()
@@ -81,7 +96,7 @@ fn part2(machines: &[ClawMachine]) -> String {
         .filter_map(min_tokens)
         .map(|(a, b)| 3 * a + b)
         .sum::<i64>()
-        .to_string()
+        .into()
 }
 
 fn min_tokens(machine: &ClawMachine) -> Option<(i64, i64)> {
@@ -92,7 +107,7 @@ fn min_tokens(machine: &ClawMachine) -> Option<(i64, i64)> {
         // so I haven't bothered and am just leaving the panic.
         panic!("singular");
     }
-    let b = machine.prize.mapv(|x| Rational64::from_integer(x));
+    let b = machine.prize.mapv(Rational64::from_integer);
     let x = machine.inv().dot(&b).into_flat();
     match x[0].is_integer() && x[1].is_integer() {
         true => Some((x[0].to_integer(), x[1].to_integer())),
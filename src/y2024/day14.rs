@@ -0,0 +1,158 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use ndarray::{azip, Array1, Array2};
+use regex::Regex;
+
+use crate::{
+    error::Locate, observe::Event, util::parse::captures_exact, Answer, Context, LogLevel, Param,
+    ParamKind, PartResult, SolveError, Solver,
+};
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pub origin: Array1<i64>,   // [row, col]
+    pub position: Array1<i64>, // [row, col]
+    pub velocity: Array1<i64>, // [v_row, v_col]
+}
+
+pub struct Lobby {
+    pub robots: Vec<Robot>,
+    pub space: Array1<i64>, // [rows, cols]
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input<'a> = Lobby;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "space",
+        kind: ParamKind::Size,
+        default: "103x101",
+        description: "Rows x columns of the lobby (7x11 in the example)",
+    }];
+
+    fn parse(input: &str, cx: &Context) -> Result<Lobby, SolveError> {
+        let robots = parse(input)?;
+        let (rows, cols) = cx.size("space");
+        if rows == 0 || cols == 0 {
+            return Err(SolveError::param("space", "the lobby can't be empty"));
+        }
+        let space = Array1::from_vec(vec![rows as i64, cols as i64]);
+        Ok(Lobby { robots, space })
+    }
+
+    fn part1(lobby: &Lobby, _cx: &Context) -> PartResult {
+        Ok(part1(lobby.robots.clone(), &lobby.space))
+    }
+
+    fn part2(lobby: &Lobby, cx: &Context) -> PartResult {
+        part2(lobby.robots.clone(), &lobby.space, cx)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Robot>, SolveError> {
+    let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)")?;
+    let robots = captures_exact(&re, input)
+        .map(|c| {
+            let (_, [px, py, vx, vy]) = c?.extract();
+            let parse = |x: &str| x.parse().at(input, x);
+            // note we switch from x,y to r,c here
+            Ok(Robot {
+                origin: Array1::from_vec(vec![parse(py)?, parse(px)?]),
+                position: Array1::from_vec(vec![parse(py)?, parse(px)?]),
+                velocity: Array1::from_vec(vec![parse(vy)?, parse(vx)?]),
+            })
+        })
+        .collect::<Result<_, SolveError>>()?;
+    Ok(robots)
+}
+
+fn part1(mut robots: Vec<Robot>, space: &Array1<i64>) -> Answer {
+    for _ in 0..100 {
+        tick(&mut robots, space);
+    }
+    let mut counts: [u64; 4] = [0, 0, 0, 0];
+    let quadrant_digit = |r: &Robot, i| r.position[i].cmp(&(space[i] / 2));
+    for robot in robots {
+        match (quadrant_digit(&robot, 0), quadrant_digit(&robot, 1)) {
+            (Ordering::Equal, _) | (_, Ordering::Equal) => {}
+            (Ordering::Less, Ordering::Less) => counts[0] += 1,
+            (Ordering::Less, Ordering::Greater) => counts[1] += 1,
+            (Ordering::Greater, Ordering::Less) => counts[2] += 1,
+            (Ordering::Greater, Ordering::Greater) => counts[3] += 1,
+        }
+    }
+    counts.into_iter().reduce(|acc, c| acc * c).unwrap().into()
+}
+
+fn part2(mut robots: Vec<Robot>, space: &Array1<i64>, cx: &Context) -> PartResult {
+    tick(&mut robots, space);
+    let mut ticks: u64 = 1;
+    while robots.iter().any(|r| r.position != r.origin) {
+        cx.check()?;
+        tick(&mut robots, space);
+        ticks += 1;
+        cx.observe(|| Event::Frame {
+            step: ticks,
+            grid: frame(&robots, space),
+        });
+        let score = xmas_score(&robots);
+        if score < 500 {
+            continue;
+        }
+        cx.log(LogLevel::Info, || {
+            format!("tree after {ticks} ticks, score {score}")
+        });
+        cx.observe(|| Event::Chosen {
+            step: ticks,
+            description: format!("Tick {ticks} - Score {score}"),
+        });
+        break;
+    }
+    cx.observe(|| Event::Counter {
+        name: "ticks",
+        value: ticks,
+    });
+    Ok(ticks.into())
+}
+
+fn tick(robots: &mut [Robot], space: &Array1<i64>) {
+    for robot in robots.iter_mut() {
+        robot.position += &robot.velocity;
+        azip!((p in &mut robot.position, &s in space) *p = p.rem_euclid(s));
+    }
+}
+
+fn xmas_score(robots: &[Robot]) -> u64 {
+    let positions: HashSet<_> = robots
+        .iter()
+        .map(|r| (r.position[0], r.position[1]))
+        .collect();
+    let corners = [
+        Array1::from_vec(vec![-1, -1]),
+        Array1::from_vec(vec![-1, 1]),
+        Array1::from_vec(vec![1, 1]),
+        Array1::from_vec(vec![1, -1]),
+    ];
+    robots
+        .iter()
+        .map(|r| {
+            corners
+                .iter()
+                .map(|c| &r.position + c)
+                .map(|p| positions.contains(&(p[0], p[1])) as u64)
+                .sum::<u64>()
+        })
+        .sum::<u64>()
+}
+
+fn frame(robots: &[Robot], space: &Array1<i64>) -> Array2<char> {
+    let size = space.mapv(|s| s as usize);
+    let mut grid = Array2::from_elem((size[0], size[1]), ' ');
+    for robot in robots {
+        let position = robot.position.mapv(|p| p as usize);
+        grid[(position[0], position[1])] = 'X';
+    }
+    grid
+}
@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+use ndarray::Array2;
+
+use crate::{observe::Event, Context, PartResult, SolveError, Solver};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub position: (usize, usize),
+}
+
+impl Item {
+    fn new(kind: ItemKind, position: (usize, usize)) -> Self {
+        Self { kind, position }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ItemKind {
+    Wall,
+    Box1,
+    Box2,
+    Robot,
+}
+
+pub type Warehouse = Vec<Item>;
+
+#[derive(Debug)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input<'a> = (Warehouse, Vec<Move>);
+
+    fn parse(input: &str, _cx: &Context) -> Result<(Warehouse, Vec<Move>), SolveError> {
+        parse(input)
+    }
+
+    fn part1((warehouse, moves): &(Warehouse, Vec<Move>), cx: &Context) -> PartResult {
+        move_and_score(warehouse.clone(), moves, cx)
+    }
+
+    fn part2((warehouse, moves): &(Warehouse, Vec<Move>), cx: &Context) -> PartResult {
+        let expanded = expand_warehouse(warehouse.clone());
+        move_and_score(expanded, moves, cx)
+    }
+}
+
+fn parse(input: &str) -> Result<(Warehouse, Vec<Move>), SolveError> {
+    let blank = input.find("\n\n").ok_or_else(|| {
+        SolveError::missing(input, "No blank line between warehouse map and move list")
+    })?;
+    let (warehouse, moves) = input.split_at(blank);
+    let unexpected = |line: &str, i: usize, x: char, what: &str| {
+        let span = &line[i..i + x.len_utf8()];
+        SolveError::input(input, span, format!("Unexpected character in {what}"))
+    };
+    let warehouse = warehouse
+        .lines()
+        .enumerate()
+        .flat_map(|(r, row)| {
+            row.char_indices()
+                .enumerate()
+                .filter_map(move |(c, (i, x))| match x {
+                    '.' => None,
+                    '#' => Some(Ok(Item::new(ItemKind::Wall, (r, c)))),
+                    'O' => Some(Ok(Item::new(ItemKind::Box1, (r, c)))),
+                    '@' => Some(Ok(Item::new(ItemKind::Robot, (r, c)))),
+                    _ => Some(Err(unexpected(row, i, x, "warehouse map"))),
+                })
+        })
+        .collect::<Result<Warehouse, _>>()?;
+    let mut robots = warehouse.iter().filter(|item| item.kind == ItemKind::Robot);
+    if robots.next().is_none() {
+        return Err(SolveError::missing(
+            input,
+            "Expected a robot (@) in the warehouse map",
+        ));
+    }
+    if robots.next().is_some() {
+        let (second, _) = input.match_indices('@').nth(1).unwrap();
+        let span = &input[second..second + 1];
+        return Err(SolveError::input(input, span, "Expected only one robot"));
+    }
+    let moves = moves
+        .lines()
+        .flat_map(|line| {
+            line.char_indices().map(move |(i, c)| match c {
+                '^' => Ok(Move::Up),
+                'v' => Ok(Move::Down),
+                '<' => Ok(Move::Left),
+                '>' => Ok(Move::Right),
+                _ => Err(unexpected(line, i, c, "move list")),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((warehouse, moves))
+}
+
+fn expand_warehouse(warehouse: Warehouse) -> Warehouse {
+    warehouse
+        .into_iter()
+        .flat_map(|item| {
+            let p1 = (item.position.0, 2 * item.position.1);
+            let p2 = (item.position.0, 2 * item.position.1 + 1);
+            match item.kind {
+                ItemKind::Wall => {
+                    vec![Item::new(ItemKind::Wall, p1), Item::new(ItemKind::Wall, p2)]
+                }
+                ItemKind::Box1 => vec![Item::new(ItemKind::Box2, p1)],
+                ItemKind::Robot => vec![Item::new(ItemKind::Robot, p1)],
+                _ => unreachable!(),
+            }
+        })
+        .collect()
+}
+
+fn frame(warehouse: &Warehouse) -> Array2<char> {
+    let rows = warehouse.iter().map(|item| item.position.0 + 1).max();
+    // A wide box takes up the cell to its right as well
+    let cols = warehouse.iter().map(|item| match item.kind {
+        ItemKind::Box2 => item.position.1 + 2,
+        _ => item.position.1 + 1,
+    });
+    let shape = (rows.unwrap_or(0), cols.max().unwrap_or(0));
+    let mut buffer = Array2::from_elem(shape, '.');
+    for item in warehouse {
+        match item.kind {
+            ItemKind::Wall => buffer[item.position] = '#',
+            ItemKind::Box1 => buffer[item.position] = 'O',
+            ItemKind::Box2 => {
+                let (r, c) = item.position;
+                buffer[item.position] = '[';
+                buffer[(r, c + 1)] = ']';
+            }
+            ItemKind::Robot => buffer[item.position] = '@',
+        }
+    }
+    buffer
+}
+
+fn move_and_score(mut warehouse: Warehouse, moves: &[Move], cx: &Context) -> PartResult {
+    cx.observe(|| Event::Frame {
+        step: 0,
+        grid: frame(&warehouse),
+    });
+    for (step, mve) in (1..).zip(moves) {
+        cx.check()?;
+        apply_move(&mut warehouse, mve);
+        cx.observe(|| Event::Frame {
+            step,
+            grid: frame(&warehouse),
+        });
+    }
+    cx.observe(|| Event::Counter {
+        name: "moves",
+        value: moves.len() as u64,
+    });
+    let score = warehouse
+        .into_iter()
+        .filter_map(|item| match (item.kind, item.position) {
+            (ItemKind::Box1 | ItemKind::Box2, (r, c)) => Some(100 * r + c),
+            _ => None,
+        })
+        .sum::<usize>();
+    Ok(score.into())
+}
+
+fn apply_move(warehouse: &mut Warehouse, mve: &Move) {
+    let robot = warehouse
+        .iter()
+        .position(|x| matches!(x.kind, ItemKind::Robot))
+        .unwrap();
+    let mut moving = HashSet::new();
+    let mut stack = vec![robot];
+    while let Some(i) = stack.pop() {
+        if moving.contains(&i) {
+            continue;
+        }
+        // The cells whose next cell over the item pushes into
+        let p = warehouse[i].position;
+        let fronts = match (warehouse[i].kind, mve) {
+            (ItemKind::Wall, _) => return,
+            (ItemKind::Box2, Move::Up | Move::Down) => vec![p, (p.0, p.1 + 1)],
+            (ItemKind::Box2, Move::Right) => vec![(p.0, p.1 + 1)],
+            _ => vec![p],
+        };
+        moving.insert(i);
+        for front in fronts {
+            // Off the map is as good as a wall
+            let Some(next) = delta(front, mve) else {
+                return;
+            };
+            stack.extend(index_of_item_at(warehouse, next));
+        }
+    }
+    for i in moving {
+        let item = &mut warehouse[i];
+        if let Some(position) = delta(item.position, mve) {
+            item.position = position;
+        }
+    }
+}
+
+fn delta((r, c): (usize, usize), mve: &Move) -> Option<(usize, usize)> {
+    match mve {
+        Move::Up => Some((r.checked_sub(1)?, c)),
+        Move::Down => Some((r + 1, c)),
+        Move::Left => Some((r, c.checked_sub(1)?)),
+        Move::Right => Some((r, c + 1)),
+    }
+}
+
+fn index_of_item_at(warehouse: &Warehouse, p: (usize, usize)) -> Option<usize> {
+    warehouse.iter().position(|x| match x.kind {
+        ItemKind::Wall | ItemKind::Robot | ItemKind::Box1 if x.position == p => true,
+        ItemKind::Box2
+            if x.position.0 == p.0 && (x.position.1 == p.1 || x.position.1 + 1 == p.1) =>
+        {
+            true
+        }
+        _ => false,
+    })
+}
@@ -5,10 +5,10 @@ use std::{
 
 use ndarray::Array2;
 
-use crate::{util::grid::Array2Ext, SolveError, SolveResult};
+use crate::{util::grid::Array2Ext, Answer, Context, PartResult, SolveError, Solver};
 
 #[derive(PartialEq)]
-enum MazePoint {
+pub enum MazePoint {
     Start,
     End,
     Empty,
@@ -16,7 +16,7 @@ enum MazePoint {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Direction {
+pub enum Direction {
     North,
     East,
     South,
@@ -24,7 +24,7 @@ enum Direction {
 }
 
 #[derive(Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Position((usize, usize), Direction);
+pub struct Position(pub (usize, usize), pub Direction);
 
 impl Position {
     fn counterclockwise(&self) -> Self {
@@ -58,59 +58,122 @@ impl Position {
     }
 }
 
-type PathMap = HashMap<Position, (usize, Vec<Position>)>;
-type Maze = Array2<MazePoint>;
+pub type PathMap = HashMap<Position, (usize, Vec<Position>)>;
+pub type Maze = Array2<MazePoint>;
 
-pub fn solve(input: &str) -> SolveResult {
-    let maze = parse(input)?;
-    let (path_map, end) = navigate(&maze);
-    Ok((part1(&path_map, end), part2(&path_map, end)))
+/// The maze, plus the best score to each reachable position and where
+/// the best paths came from, which are filled in by `prepare`.
+pub struct Paths {
+    pub maze: Maze,
+    pub path_map: PathMap,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input<'a> = Paths;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Paths, SolveError> {
+        let maze = parse(input)?;
+        Ok(Paths {
+            start: locate(input, &maze, MazePoint::Start, 'S')?,
+            end: locate(input, &maze, MazePoint::End, 'E')?,
+            maze,
+            path_map: PathMap::new(),
+        })
+    }
+
+    fn prepare(paths: &mut Paths, cx: &Context) -> Result<(), SolveError> {
+        paths.path_map = navigate(&paths.maze, paths.start, paths.end, cx)?;
+        Ok(())
+    }
+
+    fn part1(paths: &Paths, _cx: &Context) -> PartResult {
+        Ok(part1(&paths.path_map, paths.end))
+    }
+
+    fn part2(paths: &Paths, _cx: &Context) -> PartResult {
+        Ok(part2(&paths.path_map, paths.end))
+    }
 }
 
 fn parse(input: &str) -> Result<Maze, SolveError> {
-    let maze = Maze::from_string(input, |c| match c {
+    Maze::try_from_string(input, |c| match c {
         'S' => Ok(MazePoint::Start),
         'E' => Ok(MazePoint::End),
         '#' => Ok(MazePoint::Wall),
         '.' => Ok(MazePoint::Empty),
         _ => Err("Unexpected character in input"),
-    })?;
-    let maze = Maze::from_shape_vec(maze.raw_dim(), maze.into_iter().collect::<Result<_, _>>()?)?;
-    Ok(maze)
+    })
+}
+
+// Where the maze's only `point` is, which is written `c` in the input
+fn locate(
+    input: &str,
+    maze: &Maze,
+    point: MazePoint,
+    c: char,
+) -> Result<(usize, usize), SolveError> {
+    let mut found = maze
+        .indexed_iter()
+        .filter(|(_, x)| **x == point)
+        .map(|(i, _)| i);
+    let first = found
+        .next()
+        .ok_or_else(|| SolveError::missing(input, format!("Expected an {c} in the maze")))?;
+    if found.next().is_some() {
+        let (second, _) = input.match_indices(c).nth(1).unwrap();
+        let span = &input[second..second + 1];
+        return Err(SolveError::input(
+            input,
+            span,
+            format!("Expected only one {c}"),
+        ));
+    }
+    Ok(first)
 }
 
-fn part1(path_map: &PathMap, end: (usize, usize)) -> String {
-    path_map[&Position(end, Direction::East)].0.to_string()
+fn part1(path_map: &PathMap, end: (usize, usize)) -> Answer {
+    match path_map.get(&Position(end, Direction::East)) {
+        Some(&(score, _)) => score.into(),
+        None => Answer::Absent("no path to the end".into()),
+    }
 }
 
-fn part2(path_map: &PathMap, end: (usize, usize)) -> String {
+fn part2(path_map: &PathMap, end: (usize, usize)) -> Answer {
+    let end = Position(end, Direction::East);
+    if !path_map.contains_key(&end) {
+        return Answer::Absent("no path to the end".into());
+    }
     let mut tiles = HashSet::new();
-    let mut stack = vec![Position(end, Direction::East)];
+    let mut stack = vec![end];
     while let Some(current) = stack.pop() {
         tiles.insert(current.0);
         stack.extend(&path_map[&current].1);
     }
 
-    tiles.len().to_string()
+    tiles.len().into()
 }
 
-fn navigate(maze: &Maze) -> (PathMap, (usize, usize)) {
-    let start = maze
-        .indexed_iter()
-        .find(|(_, x)| **x == MazePoint::Start)
-        .map(|(i, _)| i)
-        .unwrap();
-    let end = maze
-        .indexed_iter()
-        .find(|(_, x)| **x == MazePoint::End)
-        .map(|(i, _)| i)
-        .unwrap();
+fn navigate(
+    maze: &Maze,
+    start: (usize, usize),
+    end: (usize, usize),
+    cx: &Context,
+) -> Result<PathMap, SolveError> {
     let mut path_map = HashMap::new();
     let mut priority_queue = BinaryHeap::new();
     let start_position = Position(start, Direction::East);
     path_map.insert(start_position, (0, vec![]));
     priority_queue.push(Reverse((0, start_position)));
+    let mut steps: u64 = 0;
     while let Some(Reverse((_, position))) = priority_queue.pop() {
+        steps += 1;
+        if steps.is_multiple_of(4096) {
+            cx.check()?;
+        }
         let (score, _) = path_map[&position];
         if position.0 == end {
             if position.1 != Direction::East {
@@ -151,5 +214,5 @@ fn navigate(maze: &Maze) -> (PathMap, (usize, usize)) {
         }
     }
 
-    return (path_map, end);
+    Ok(path_map)
 }
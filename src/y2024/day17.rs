@@ -0,0 +1,287 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+use regex::Regex;
+
+use crate::{
+    error::Locate, observe::Event, Answer, Context, LogLevel, PartResult, SolveError, Solver,
+};
+
+pub type Program = Vec<Instruction>;
+
+pub struct Computer {
+    pub program: Program,
+    pub raw_program: Vec<u8>,
+    pub register_a: u64,
+}
+
+#[derive(Default)]
+struct State {
+    registers: [u64; 3],
+    instruction_pointer: usize,
+    output: Vec<u8>,
+}
+
+impl State {
+    fn run(&mut self, program: &Program, reg_a: u64, cx: &Context) -> Result<(), SolveError> {
+        self.registers[Register::A as usize] = reg_a;
+        self.registers[Register::B as usize] = 0;
+        self.registers[Register::C as usize] = 0;
+        self.instruction_pointer = 0;
+        self.output.clear();
+        let mut steps: u32 = 0;
+        while let Some(instruction) = program.get(self.instruction_pointer) {
+            if self.execute(instruction) {
+                self.instruction_pointer += 1;
+            }
+            // The program may never halt
+            steps = steps.wrapping_add(1);
+            if steps.is_multiple_of(4096) {
+                cx.check()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, instr: &Instruction) -> bool {
+        match instr {
+            Instruction::Adv(operand) => {
+                self.registers[Register::A as usize] /= 2_u64.pow(operand.value(self) as u32)
+            }
+            Instruction::Bxl(operand) => {
+                self.registers[Register::B as usize] ^= *operand as u64;
+            }
+            Instruction::Bst(operand) => {
+                self.registers[Register::B as usize] = operand.value(self) % 8
+            }
+            Instruction::Jnz(operand) => {
+                if self.registers[Register::A as usize] != 0 {
+                    self.instruction_pointer = *operand as usize / 2;
+                    return false;
+                }
+            }
+            Instruction::Bxc => {
+                self.registers[Register::B as usize] ^= self.registers[Register::C as usize]
+            }
+            Instruction::Out(operand) => self.output.push((operand.value(self) % 8) as u8),
+            Instruction::Bdv(operand) => {
+                self.registers[Register::B as usize] =
+                    self.registers[Register::A as usize] / 2_u64.pow(operand.value(self) as u32)
+            }
+            Instruction::Cdv(operand) => {
+                self.registers[Register::C as usize] =
+                    self.registers[Register::A as usize] / 2_u64.pow(operand.value(self) as u32)
+            }
+        }
+        true
+    }
+}
+
+#[repr(usize)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+trait Operand {
+    fn value(&self, computer: &State) -> u64;
+}
+
+pub type LiteralOperand = u8;
+impl Operand for LiteralOperand {
+    fn value(&self, _: &State) -> u64 {
+        *self as u64
+    }
+}
+
+pub enum ComboOperand {
+    Literal(u8),
+    Register(Register),
+}
+
+impl ComboOperand {
+    fn from(operand: u8) -> Result<Self, &'static str> {
+        match operand {
+            0..=3 => Ok(ComboOperand::Literal(operand)),
+            4 => Ok(ComboOperand::Register(Register::A)),
+            5 => Ok(ComboOperand::Register(Register::B)),
+            6 => Ok(ComboOperand::Register(Register::C)),
+            _ => Err("Combo operand 7 is reserved"),
+        }
+    }
+}
+impl Operand for ComboOperand {
+    fn value(&self, computer: &State) -> u64 {
+        match self {
+            ComboOperand::Literal(v) => *v as u64,
+            ComboOperand::Register(r) => computer.registers[*r as usize],
+        }
+    }
+}
+
+pub enum Instruction {
+    Adv(ComboOperand),
+    Bxl(LiteralOperand),
+    Bst(ComboOperand),
+    Jnz(LiteralOperand),
+    Bxc,
+    Out(ComboOperand),
+    Bdv(ComboOperand),
+    Cdv(ComboOperand),
+}
+
+impl Instruction {
+    // Fails with what's wrong with the operand
+    fn from(opcode: u8, operand: u8) -> Result<Self, &'static str> {
+        Ok(match opcode {
+            0 => Instruction::Adv(ComboOperand::from(operand)?),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(ComboOperand::from(operand)?),
+            // The instruction pointer counts instructions, which are two numbers each
+            3 if !operand.is_multiple_of(2) => return Err("Jump targets must be even"),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(ComboOperand::from(operand)?),
+            6 => Instruction::Bdv(ComboOperand::from(operand)?),
+            7 => Instruction::Cdv(ComboOperand::from(operand)?),
+            _ => return Err("Invalid opcode"),
+        })
+    }
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input<'a> = Computer;
+
+    fn parse(input: &str, _cx: &Context) -> Result<Computer, SolveError> {
+        let (fields, register_a) = parse(input)?;
+        if fields.len() % 2 != 0 {
+            return Err(SolveError::missing(
+                input,
+                "Expected an operand after the last opcode",
+            ));
+        }
+        let program = fields
+            .chunks_exact(2)
+            .map(|pair| {
+                let [(_, opcode), (span, operand)] = [pair[0], pair[1]];
+                Instruction::from(opcode, operand)
+                    .map_err(|message| SolveError::input(input, span, message))
+            })
+            .collect::<Result<_, _>>()?;
+        let raw_program = fields.iter().map(|&(_, x)| x).collect();
+        Ok(Computer {
+            program,
+            raw_program,
+            register_a,
+        })
+    }
+
+    fn part1(computer: &Computer, cx: &Context) -> PartResult {
+        part1(&computer.program, computer.register_a, cx)
+    }
+
+    fn part2(computer: &Computer, cx: &Context) -> PartResult {
+        part2(&computer.program, &computer.raw_program, cx)
+    }
+}
+
+// Each number of the program along with where it is in the input
+type Fields<'a> = Vec<(&'a str, u8)>;
+
+fn parse(input: &str) -> Result<(Fields<'_>, u64), SolveError> {
+    let blank = Regex::new(r"\r?\n\r?\n")?;
+    if let [registers, program] = blank.splitn(input, 2).collect::<Vec<_>>()[..] {
+        let re = Regex::new(r"Register\s*A:\s*(\d+)")?;
+        let reg_a = re
+            .captures(registers)
+            .ok_or_else(|| SolveError::input(input, registers, "Couldn't find register A"))?;
+        let (_, [reg_a]) = reg_a.extract();
+        let reg_a = reg_a.parse().at(input, reg_a)?;
+
+        let re = Regex::new(r"Program:\s*([,\d]+)")?;
+        let raw_program = re
+            .captures(program)
+            .ok_or_else(|| SolveError::input(input, program, "Couldn't parse program"))?;
+        let (_, [raw_program]) = raw_program.extract();
+        let raw_program = raw_program
+            .split(",")
+            .map(|x| match x.parse().at(input, x)? {
+                value @ 0..=7 => Ok((x, value)),
+                _ => Err(SolveError::input(input, x, "Expected a 3-bit number")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((raw_program, reg_a))
+    } else {
+        Err(SolveError::missing(
+            input,
+            "Couldn't find blank line between registers and program",
+        ))
+    }
+}
+
+fn part1(program: &Program, reg_a: u64, cx: &Context) -> PartResult {
+    let mut state = State::default();
+    state.run(program, reg_a, cx)?;
+    Ok(state
+        .output
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+        .into())
+}
+
+fn part2(program: &Program, target: &[u8], cx: &Context) -> PartResult {
+    let mut checked: HashSet<u64> = HashSet::new();
+    let mut priority_queue = BinaryHeap::new();
+    priority_queue.push(Reverse((usize::MAX, 0)));
+    let mut best = usize::MAX;
+    while let Some(Reverse((s, reg_a))) = priority_queue.pop() {
+        if s < best {
+            best = s;
+            cx.log(LogLevel::Debug, || {
+                format!("closest so far: A = {reg_a}, score {s}")
+            });
+        }
+        if s == 0 {
+            cx.log(LogLevel::Info, || {
+                format!("explored {} candidates", checked.len())
+            });
+            cx.observe(|| Event::Counter {
+                name: "candidates",
+                value: checked.len() as u64,
+            });
+            return Ok(reg_a.into());
+        }
+        cx.check()?;
+        let neighbors = (0..=63)
+            .map(|i| reg_a ^ (1 << i))
+            .filter(|x| !checked.contains(x))
+            .collect::<Vec<_>>();
+        checked.extend(neighbors.iter().copied());
+        for x in neighbors {
+            priority_queue.push(Reverse((score(program, target, x, cx)?, x)));
+        }
+    }
+    Ok(Answer::Absent(
+        "no value of register A outputs the program".into(),
+    ))
+}
+
+fn score(program: &Program, target: &[u8], reg_a: u64, cx: &Context) -> Result<usize, SolveError> {
+    let mut state = State::default();
+    state.run(program, reg_a, cx)?;
+    Ok(10 * target.len().abs_diff(state.output.len())
+        + target
+            .iter()
+            .zip(state.output)
+            .map(|(x, y)| x.abs_diff(y) as usize)
+            .sum::<usize>())
+}
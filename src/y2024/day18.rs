@@ -0,0 +1,139 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use ndarray::Array2;
+use regex::Regex;
+
+use crate::{
+    error::Locate,
+    util::{grid::ArrayExt, parse::captures_exact},
+    Answer, Context, Param, ParamKind, PartResult, SolveError, Solver,
+};
+
+pub type Memory = Array2<bool>;
+
+pub struct MemorySpace {
+    pub falling_bytes: Vec<[usize; 2]>, // [row, col]
+    pub size: (usize, usize),
+    pub partial: usize,
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input<'a> = MemorySpace;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "bytes",
+            kind: ParamKind::Integer,
+            default: "1024",
+            description: "Bytes fallen before part 1 looks for a path (12 in the example)",
+        },
+        Param {
+            name: "size",
+            kind: ParamKind::Integer,
+            default: "71",
+            description: "Width and height of the memory space (7 in the example)",
+        },
+    ];
+
+    fn parse(input: &str, cx: &Context) -> Result<MemorySpace, SolveError> {
+        let size = cx.integer("size");
+        if size == 0 {
+            return Err(SolveError::param("size", "the memory space can't be empty"));
+        }
+        let falling_bytes = parse(input, size)?;
+        let partial = cx.integer("bytes");
+        if partial > falling_bytes.len() {
+            let message = format!("the input only has {} bytes", falling_bytes.len());
+            return Err(SolveError::param("bytes", message));
+        }
+        Ok(MemorySpace {
+            falling_bytes,
+            size: (size, size),
+            partial,
+        })
+    }
+
+    fn part1(space: &MemorySpace, _cx: &Context) -> PartResult {
+        let mut memory = Memory::from_elem(space.size, true);
+        Ok(part1(&space.falling_bytes, &mut memory, space.partial))
+    }
+
+    fn part2(space: &MemorySpace, cx: &Context) -> PartResult {
+        let mut memory = Memory::from_elem(space.size, true);
+        for ix in &space.falling_bytes[..space.partial] {
+            memory[*ix] = false;
+        }
+        part2(&space.falling_bytes, &mut memory, space.partial, cx)
+    }
+}
+
+fn parse(input: &str, size: usize) -> Result<Vec<[usize; 2]>, SolveError> {
+    let re = Regex::new(r"(\d+),(\d+)")?;
+    captures_exact(&re, input)
+        .map(|c| {
+            let (byte, [x, y]) = c?.extract();
+            let ix = [y.parse().at(input, y)?, x.parse().at(input, x)?]; // switch from x,y to r,c
+            if ix.iter().any(|&i| i >= size) {
+                let message = format!("Byte falls outside the {size}x{size} memory space");
+                return Err(SolveError::input(input, byte, message));
+            }
+            Ok(ix)
+        })
+        .collect()
+}
+
+fn part1(falling_bytes: &[[usize; 2]], memory: &mut Memory, partial: usize) -> Answer {
+    for ix in &falling_bytes[..partial] {
+        memory[*ix] = false;
+    }
+    match shortest_path(memory) {
+        Some(d) => d.into(),
+        None => Answer::Absent("no path to the exit".into()),
+    }
+}
+
+fn part2(
+    falling_bytes: &[[usize; 2]],
+    memory: &mut Memory,
+    partial: usize,
+    cx: &Context,
+) -> PartResult {
+    for ix in &falling_bytes[partial..] {
+        cx.check()?;
+        memory[*ix] = false;
+        match shortest_path(memory) {
+            Some(_) => continue,
+            None => return Ok(Answer::Coordinate { x: ix[1], y: ix[0] }), // back to x,y
+        }
+    }
+    Ok(Answer::Absent("the exit is never cut off".into()))
+}
+
+fn shortest_path(memory: &Memory) -> Option<usize> {
+    let start = (0, 0);
+    let end = (memory.shape()[0] - 1, memory.shape()[1] - 1);
+    let mut shortest = HashMap::new();
+    let mut priority_queue = BinaryHeap::new();
+    shortest.insert(start, 0);
+    priority_queue.push(Reverse((0, 0, start)));
+    while let Some(Reverse((_, d, ix))) = priority_queue.pop() {
+        if ix == end {
+            return Some(d);
+        }
+        let neighbors = memory.neighbors(ix).filter(|n_ix| memory[*n_ix]);
+        for n_ix in neighbors {
+            if !matches!(shortest.get(&n_ix), Some(ex) if *ex <= d + 1) {
+                let d = d + 1;
+                let s = d + n_ix.0.abs_diff(end.0) + n_ix.1.abs_diff(end.1);
+                shortest.insert(n_ix, d);
+                priority_queue.push(Reverse((s, d, n_ix)));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,67 @@
+use regex::Regex;
+
+use crate::{Answer, Context, PartResult, SolveError, Solver};
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Input<'a> = (Vec<&'a str>, Vec<&'a str>);
+
+    fn parse<'a>(
+        input: &'a str,
+        _cx: &Context,
+    ) -> Result<(Vec<&'a str>, Vec<&'a str>), SolveError> {
+        parse(input)
+    }
+
+    fn part1((towels, designs): &(Vec<&str>, Vec<&str>), cx: &Context) -> PartResult {
+        Ok(part1(&counts(towels, designs, cx)?))
+    }
+
+    fn part2((towels, designs): &(Vec<&str>, Vec<&str>), cx: &Context) -> PartResult {
+        Ok(part2(&counts(towels, designs, cx)?))
+    }
+}
+
+fn parse(input: &str) -> Result<(Vec<&str>, Vec<&str>), SolveError> {
+    let blank = Regex::new(r"\r?\n\r?\n")?;
+    if let [towels, designs] = blank.splitn(input, 2).collect::<Vec<_>>()[..] {
+        let towels = towels.split(", ").collect::<Vec<_>>();
+        let designs = designs.lines().collect::<Vec<_>>();
+        Ok((towels, designs))
+    } else {
+        Err(SolveError::missing(
+            input,
+            "Couldn't find blank line between towels and designs",
+        ))
+    }
+}
+
+fn part1(counts: &[usize]) -> Answer {
+    counts.iter().filter(|x| **x > 0).count().into()
+}
+
+fn part2(counts: &[usize]) -> Answer {
+    counts.iter().sum::<usize>().into()
+}
+
+// The ways to make each design
+fn counts(towels: &Vec<&str>, designs: &[&str], cx: &Context) -> Result<Vec<usize>, SolveError> {
+    designs
+        .iter()
+        .map(|d| cx.check().map(|()| count(towels, d)))
+        .collect()
+}
+
+fn count(towels: &Vec<&str>, design: &str) -> usize {
+    let mut counts = vec![0; design.len() + 1];
+    counts[0] = 1;
+    for i in 0..=design.len() {
+        for t in towels {
+            if matches!(design.get(i..i + t.len()), Some(d) if *t == d) {
+                counts[i + t.len()] += counts[i]
+            }
+        }
+    }
+    counts[design.len()]
+}
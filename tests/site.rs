@@ -0,0 +1,137 @@
+// Runs the commands that talk to the puzzle site against a local stand-in,
+// pointed at with AOC_URL.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tiny_http::{Response, Server};
+
+const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+const RIGHT: &str = "<html><body><main><article><p>That's the right answer! \
+                     You are <span class=\"day-success\">one gold star</span> closer to finding \
+                     the Chief Historian.</p></article></main></body></html>";
+
+struct Received {
+    method: String,
+    url: String,
+    cookie: Option<String>,
+    user_agent: Option<String>,
+    body: String,
+}
+
+// Answers like the site does and keeps every request it got
+fn mock_site() -> (String, Arc<Mutex<Vec<Received>>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&received);
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let header = |name: &'static str| {
+                let header = request.headers().iter().find(|h| h.field.equiv(name));
+                header.map(|h| h.value.to_string())
+            };
+            let (cookie, user_agent) = (header("Cookie"), header("User-Agent"));
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let response = match (request.method().as_str(), request.url()) {
+                ("GET", "/2024/day/1/input") => Response::from_string(INPUT),
+                ("POST", "/2024/day/1/answer") => Response::from_string(RIGHT),
+                _ => Response::from_string("Not found").with_status_code(404),
+            };
+            log.lock().unwrap().push(Received {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                cookie,
+                user_agent,
+                body,
+            });
+            request.respond(response).unwrap();
+        }
+    });
+    (format!("http://127.0.0.1:{port}"), received)
+}
+
+fn solve(dir: &Path, url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(args)
+        .env("AOC_DIR", dir)
+        .env("AOC_URL", url)
+        .env("AOC_SESSION", "token")
+        .env_remove("AOC_LOG")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
+
+// A fresh directory for one test's inputs and history
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{test}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn assert_identified(received: &Received) {
+    assert_eq!(received.cookie.as_deref(), Some("session=token"));
+    let user_agent = received.user_agent.as_deref().unwrap_or_default();
+    assert!(
+        user_agent.contains(env!("CARGO_PKG_REPOSITORY")),
+        "{user_agent}"
+    );
+}
+
+#[test]
+fn fetches_each_input_once() {
+    let dir = temp_dir("fetch");
+    let (url, received) = mock_site();
+
+    let fetched = solve(&dir, &url, &["fetch", "1", "--year", "2024"]);
+    assert!(fetched.status.success(), "{fetched:?}");
+    let input = fs::read_to_string(dir.join("inputs/2024/day01.txt")).unwrap();
+    assert_eq!(input, INPUT);
+    let kept = solve(&dir, &url, &["fetch", "1", "--year", "2024"]);
+    assert!(kept.status.success(), "{kept:?}");
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        (received[0].method.as_str(), received[0].url.as_str()),
+        ("GET", "/2024/day/1/input")
+    );
+    assert_identified(&received[0]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn submits_answers_not_already_accepted() {
+    let dir = temp_dir("submit");
+    let (url, received) = mock_site();
+    fs::create_dir_all(dir.join("inputs/2024")).unwrap();
+    fs::write(dir.join("inputs/2024/day01.txt"), INPUT).unwrap();
+
+    let args = ["submit", "1", "--part", "1", "--year", "2024"];
+    let submitted = solve(&dir, &url, &args);
+    assert!(submitted.status.success(), "{submitted:?}");
+    let history = fs::read_to_string(dir.join(".submissions.jsonl")).unwrap();
+    assert!(history.contains(r#""answer":"11""#), "{history}");
+    assert!(history.contains(r#""outcome":"right""#), "{history}");
+    // The history has it right already, so the site isn't asked again
+    let again = solve(&dir, &url, &args);
+    assert!(!again.status.success(), "{again:?}");
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        (received[0].method.as_str(), received[0].url.as_str()),
+        ("POST", "/2024/day/1/answer")
+    );
+    assert_eq!(received[0].body, "level=1&answer=11");
+    assert_identified(&received[0]);
+    fs::remove_dir_all(&dir).unwrap();
+}